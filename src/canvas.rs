@@ -2,13 +2,16 @@ extern crate sdl2;
 
 use sdl2::event::EventPollIterator;
 
-pub use sdl2::pixels::Color;
 pub use sdl2::event::Event;
 pub use sdl2::keyboard::Keycode;
 use sdl2::video::WindowBuildError;
 use sdl2::IntegerOrSdlError;
+use std::error::Error;
 use std::ffi::NulError;
+use std::fmt;
+use super::color::Color;
 use super::common::Point;
+use super::renderer::{Camera, Renderer};
 use sdl2::rect::Point as SdlPoint;
 use sdl2::mouse::MouseState;
 
@@ -53,11 +56,31 @@ impl From<String> for CanvasBuildError {
     }
 }
 
+impl fmt::Display for CanvasBuildError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CanvasBuildError::IntegerOverflows(name, value) => write!(f, "{} is too large: {}", name, value),
+            CanvasBuildError::HeightOverflows(height) => write!(f, "window height is too large: {}", height),
+            CanvasBuildError::WidthOverflows(width) => write!(f, "window width is too large: {}", width),
+            CanvasBuildError::InvalidTitle(err) => write!(f, "invalid window title: {}", err),
+            CanvasBuildError::SdlError(err) => write!(f, "SDL error: {}", err)
+        }
+    }
+}
+
+impl Error for CanvasBuildError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CanvasBuildError::InvalidTitle(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
 pub struct Canvas {
     canvas: sdl2::render::WindowCanvas,
     event_pump: sdl2::EventPump,
-    camera_center: Point,
-    scale: f64
+    camera: Camera
 }
 
 impl Canvas {
@@ -75,14 +98,14 @@ impl Canvas {
 
         let event_pump = sdl_context.event_pump().map_err(CanvasBuildError::from)?;
 
-        Ok( Self { canvas, event_pump, camera_center: Point::ZERO, scale: 0.03 } )
+        Ok( Self { canvas, event_pump, camera: Camera::default() } )
     }
 
     pub fn poll_event(&mut self) -> Option<Event> {
         self.event_pump.poll_event()
     }
 
-    pub fn poll_events_iter(&mut self) -> EventPollIterator {
+    pub fn poll_events_iter(&mut self) -> EventPollIterator<'_> {
         self.event_pump.poll_iter()
     }
 
@@ -90,53 +113,45 @@ impl Canvas {
         self.event_pump.mouse_state()
     }
 
-    pub fn draw_line<T1: Into<Point>, T2: Into<Point>>(&mut self, start: T1, end: T2) -> Result<(), String> {
-        self.canvas.draw_line(
-            self.point_to_sdlpoint(start.into()),
-            self.point_to_sdlpoint(end.into())
-        )
+    pub fn get_output_size(&self) -> (u32, u32) {
+        self.canvas.output_size().unwrap()
     }
 
-    pub fn clear(&mut self) {
-        self.canvas.clear();
+    fn point_to_sdlpoint(&self, point: Point) -> SdlPoint {
+        let pixel = self.point_to_pixel(point);
+        SdlPoint::new(pixel.x.round() as i32, pixel.y.round() as i32)
     }
+}
 
-    pub fn set_draw_color<T: Into<Color>>(&mut self, color: T) {
-        self.canvas.set_draw_color(color);
+impl Renderer for Canvas {
+    fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String> {
+        self.canvas.draw_line(
+            self.point_to_sdlpoint(start),
+            self.point_to_sdlpoint(end)
+        )
     }
 
-    pub fn present(&mut self) {
-        self.canvas.present();
+    fn clear(&mut self) {
+        self.canvas.clear();
     }
 
-    pub fn set_camera_pos(&mut self, p: Point) {
-        self.camera_center = p;
+    fn set_color(&mut self, color: Color) {
+        self.canvas.set_draw_color(sdl2::pixels::Color::RGBA(color.r, color.g, color.b, color.a));
     }
 
-    pub fn move_camera_by_pixels(&mut self, x: i32, y: i32) {
-        let point = Point::new(-x as f64, y as f64);
-        self.camera_center += point.scale(self.scale.recip());
+    fn present(&mut self) {
+        self.canvas.present();
     }
 
-    pub fn zoom(&mut self, scale: f64, center_x: i32, center_y: i32) {
-        let point_before = self.coord_to_point(center_x, center_y);
-        self.scale *= scale;
-        let point_after = self.coord_to_point(center_x, center_y);
-        self.camera_center -= point_after - point_before;
+    fn get_size(&self) -> (u32, u32) {
+        self.get_output_size()
     }
 
-    fn point_to_sdlpoint(&self, point: Point) -> SdlPoint {
-        let point = (point - self.camera_center).scale(self.scale);
-        let (width, height) = self.canvas.output_size().unwrap();
-        let new_x = (point.x() + (width as f64)/2.0).round() as i32;
-        let new_y = (-point.y() + (height as f64)/2.0).round() as i32;
-        SdlPoint::new(new_x, new_y)
+    fn get_camera(&self) -> &Camera {
+        &self.camera
     }
 
-    fn coord_to_point(&self, x: i32, y: i32) -> Point {
-        let (width, height) = self.canvas.output_size().unwrap();
-        let new_x = x as f64 - (width as f64)/2.0;
-        let new_y = -y as f64 + (height as f64)/2.0;
-        Point::new(new_x, new_y).scale(self.scale.recip()) + self.camera_center
+    fn get_camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,57 @@
+// Simulated time, advanced by the real time between frames scaled by `time_scale`.
+// Accumulating instead of scaling the total elapsed time means the speed can change
+// without the time jumping.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SimulationClock {
+    time: f64,
+    time_scale: f64,
+    paused: bool
+}
+
+impl SimulationClock {
+    pub fn new(time_scale: f64) -> Self {
+        Self { time: 0.0, time_scale, paused: false }
+    }
+
+    // Advances by `dt` seconds of real time, unless paused
+    pub fn advance(&mut self, dt: f64) -> f64 {
+        if !self.paused {
+            self.time += dt * self.time_scale;
+        }
+        self.time
+    }
+
+    // Moves by `dt` seconds of real time even when paused, backwards if negative
+    pub fn step(&mut self, dt: f64) -> f64 {
+        self.time += dt * self.time_scale;
+        self.time
+    }
+
+    pub fn seek(&mut self, time: f64) {
+        self.time = time;
+    }
+
+    pub fn get_time(&self) -> f64 {
+        self.time
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        self.time_scale = time_scale;
+    }
+
+    pub fn get_time_scale(&self) -> f64 {
+        self.time_scale
+    }
+
+    pub fn set_paused(&mut self, value: bool) {
+        self.paused = value;
+    }
+
+    pub fn get_paused(&self) -> bool {
+        self.paused
+    }
+
+    pub fn toggle_paused(&mut self) {
+        self.paused = !self.paused;
+    }
+}
@@ -0,0 +1,202 @@
+use std::error::Error;
+use std::fmt;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, BufWriter, Read, Write};
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use super::fourier::CoefficientMethod;
+use super::svgpath::{Normalization, Parameterization};
+use super::world::{SVector, World};
+
+#[derive(Debug)]
+pub enum CoefficientFileError {
+    Io(io::Error),
+    JsonError(serde_json::Error),
+    InvalidCsvLine(usize, String),
+    UnknownFormat(PathBuf)
+}
+
+impl From<io::Error> for CoefficientFileError {
+    fn from(value: io::Error) -> Self {
+        CoefficientFileError::Io(value)
+    }
+}
+
+impl From<serde_json::Error> for CoefficientFileError {
+    fn from(value: serde_json::Error) -> Self {
+        CoefficientFileError::JsonError(value)
+    }
+}
+
+impl fmt::Display for CoefficientFileError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            CoefficientFileError::Io(err) => write!(f, "could not access the coefficient file: {}", err),
+            CoefficientFileError::JsonError(err) => write!(f, "invalid coefficient JSON: {}", err),
+            CoefficientFileError::InvalidCsvLine(line, text) => write!(f, "invalid coefficient CSV on line {}: {}", line, text),
+            CoefficientFileError::UnknownFormat(path) => {
+                write!(f, "unknown coefficient file format, expected .json or .csv: {}", path.display())
+            }
+        }
+    }
+}
+
+impl Error for CoefficientFileError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            CoefficientFileError::Io(err) => Some(err),
+            CoefficientFileError::JsonError(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CoefficientFormat {
+    Json,
+    Csv
+}
+
+impl CoefficientFormat {
+    pub fn from_path<P: AsRef<Path>>(path: P) -> Option<Self> {
+        let extension = path.as_ref().extension()?.to_str()?.to_ascii_lowercase();
+        match extension.as_str() {
+            "json" => Some(CoefficientFormat::Json),
+            "csv" => Some(CoefficientFormat::Csv),
+            _ => None
+        }
+    }
+}
+
+// How the coefficients were computed. Everything is optional so hand-written files
+// only need the coefficients themselves.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct CoefficientMetadata {
+    pub source: Option<String>,
+    pub samples: Option<usize>,
+    pub max_frequency: Option<u32>,
+    pub method: Option<CoefficientMethod>,
+    pub parameterization: Option<Parameterization>,
+    pub normalization: Option<Normalization>
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Coefficient {
+    pub frequency: f64,
+    pub magnitude: f64,
+    pub start_angle: f64
+}
+
+impl From<&SVector> for Coefficient {
+    fn from(svector: &SVector) -> Self {
+        Self {
+            frequency: svector.get_frequency(),
+            magnitude: svector.get_magnitude(),
+            start_angle: svector.get_start_angle()
+        }
+    }
+}
+
+impl From<Coefficient> for SVector {
+    fn from(coefficient: Coefficient) -> Self {
+        SVector::new(coefficient.start_angle, coefficient.frequency, coefficient.magnitude)
+    }
+}
+
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct CoefficientFile {
+    #[serde(default)]
+    pub metadata: CoefficientMetadata,
+    pub coefficients: Vec<Coefficient>
+}
+
+const CSV_HEADER: &str = "frequency,magnitude,start_angle";
+
+impl CoefficientFile {
+    pub fn new(metadata: CoefficientMetadata, svectors: &[SVector]) -> Self {
+        Self { metadata, coefficients: svectors.iter().map(Coefficient::from).collect() }
+    }
+
+    pub fn to_world(&self) -> World {
+        World::new(self.coefficients.iter().cloned().map(SVector::from).collect())
+    }
+
+    pub fn open<P: AsRef<Path>>(path: P) -> Result<Self, CoefficientFileError> {
+        let path = path.as_ref();
+        let format = CoefficientFormat::from_path(path)
+            .ok_or_else(|| CoefficientFileError::UnknownFormat(path.to_path_buf()))?;
+        Self::read(BufReader::new(File::open(path)?), format)
+    }
+
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> Result<(), CoefficientFileError> {
+        let path = path.as_ref();
+        let format = CoefficientFormat::from_path(path)
+            .ok_or_else(|| CoefficientFileError::UnknownFormat(path.to_path_buf()))?;
+        let mut writer = BufWriter::new(File::create(path)?);
+        self.write(&mut writer, format)?;
+        writer.flush()?;
+        Ok(())
+    }
+
+    pub fn read<R: Read>(reader: R, format: CoefficientFormat) -> Result<Self, CoefficientFileError> {
+        match format {
+            CoefficientFormat::Json => Ok(serde_json::from_reader(reader)?),
+            CoefficientFormat::Csv => Self::read_csv(BufReader::new(reader))
+        }
+    }
+
+    pub fn write<W: Write>(&self, mut writer: W, format: CoefficientFormat) -> Result<(), CoefficientFileError> {
+        match format {
+            CoefficientFormat::Json => serde_json::to_writer_pretty(&mut writer, self)?,
+            CoefficientFormat::Csv => {
+                // The metadata goes in a comment line, as JSON so it reads back the same
+                writeln!(writer, "# {}", serde_json::to_string(&self.metadata)?)?;
+                writeln!(writer, "{}", CSV_HEADER)?;
+                for c in self.coefficients.iter() {
+                    writeln!(writer, "{},{},{}", c.frequency, c.magnitude, c.start_angle)?;
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn read_csv<R: BufRead>(reader: R) -> Result<Self, CoefficientFileError> {
+        let mut file = Self::default();
+
+        for (i, line) in reader.lines().enumerate() {
+            let line = line?;
+            let line = line.trim();
+            let invalid = || CoefficientFileError::InvalidCsvLine(i + 1, line.to_string());
+
+            if line.is_empty() || line == CSV_HEADER {
+                continue;
+            }
+            if let Some(comment) = line.strip_prefix('#') {
+                if let Ok(metadata) = serde_json::from_str(comment.trim()) {
+                    file.metadata = metadata;
+                }
+                continue;
+            }
+
+            let values = line.split(',')
+                .map(|value| value.trim().parse::<f64>())
+                .collect::<Result<Vec<f64>, _>>()
+                .map_err(|_| invalid())?;
+            match values.as_slice() {
+                &[frequency, magnitude, start_angle] => {
+                    file.coefficients.push(Coefficient { frequency, magnitude, start_angle });
+                },
+                _ => return Err(invalid())
+            }
+        }
+
+        Ok(file)
+    }
+}
+
+pub fn load_world<P: AsRef<Path>>(path: P) -> Result<World, CoefficientFileError> {
+    Ok(CoefficientFile::open(path)?.to_world())
+}
@@ -0,0 +1,36 @@
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Color {
+    pub r: u8, pub g: u8, pub b: u8, pub a: u8
+}
+
+// Named like sdl2's Color constructors so either type reads the same
+#[allow(non_snake_case)]
+impl Color {
+    pub const fn RGB(r: u8, g: u8, b: u8) -> Self {
+        Self { r, g, b, a: 255 }
+    }
+
+    pub const fn RGBA(r: u8, g: u8, b: u8, a: u8) -> Self {
+        Self { r, g, b, a }
+    }
+
+    pub fn rgb(self) -> (u8, u8, u8) {
+        (self.r, self.g, self.b)
+    }
+
+    pub fn rgba(self) -> (u8, u8, u8, u8) {
+        (self.r, self.g, self.b, self.a)
+    }
+}
+
+impl From<(u8, u8, u8)> for Color {
+    fn from((r, g, b): (u8, u8, u8)) -> Self {
+        Self::RGB(r, g, b)
+    }
+}
+
+impl From<(u8, u8, u8, u8)> for Color {
+    fn from((r, g, b, a): (u8, u8, u8, u8)) -> Self {
+        Self::RGBA(r, g, b, a)
+    }
+}
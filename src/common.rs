@@ -1,4 +1,4 @@
-use std::{cmp, ops};
+use std::{cmp, iter, ops};
 
 #[derive(Copy, Clone, Debug)]
 pub struct Point {
@@ -12,12 +12,39 @@ impl Point {
         Self { x, y }
     }
 
+    pub const I: Point = Point { x: 0.0, y: 1.0 };
+
     pub fn from_ei(angle: f64) -> Self {
-        Point::new(1.0, 0.0).rotate(angle)
+        Self::new(angle.cos(), angle.sin())
     }
 
     pub fn mag(self) -> f64 {
-        (self.x.powf(2.0) + self.y.powf(2.0)).sqrt()
+        self.abs_sq().sqrt()
+    }
+
+    pub fn abs_sq(self) -> f64 {
+        self.x * self.x + self.y * self.y
+    }
+
+    pub fn conj(self) -> Self {
+        Self::new(self.x, -self.y)
+    }
+
+    pub fn recip(self) -> Self {
+        self.conj() / self.abs_sq()
+    }
+
+    pub fn exp(self) -> Self {
+        Self::from_ei(self.y) * self.x.exp()
+    }
+
+    #[deprecated(note = "use the * operator instead")]
+    pub fn complex_mult(p1: Self, p2: Self) -> Self {
+        p1 * p2
+    }
+
+    pub fn approx_eq<T: Into<Point>>(self, other: T, epsilon: f64) -> bool {
+        (self - other).mag() <= epsilon
     }
 
     pub fn angle(self) -> f64 {
@@ -35,13 +62,6 @@ impl Point {
         )
     }
 
-    pub fn complex_mult(p1: Self, p2: Self) -> Self {
-        Self::new(
-            p1.x * p2.x - p1.y * p2.y,
-            p1.x * p2.y + p1.y * p2.x
-        )
-    }
-
     pub fn x(self) -> f64 { self.x }
     
     pub fn y(self) -> f64 { self.y }
@@ -85,6 +105,57 @@ impl<T: Into<Point>> ops::SubAssign<T> for Point {
     }
 }
 
+impl<T: Into<Point>> ops::Mul<T> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self {
+        let rhs = rhs.into();
+        Self::new(
+            self.x * rhs.x - self.y * rhs.y,
+            self.x * rhs.y + self.y * rhs.x
+        )
+    }
+}
+
+impl<T: Into<Point>> ops::MulAssign<T> for Point {
+    fn mul_assign(&mut self, rhs: T) {
+        *self = *self * rhs;
+    }
+}
+
+impl ops::Mul<f64> for Point {
+    type Output = Self;
+
+    fn mul(self, rhs: f64) -> Self {
+        self.scale(rhs)
+    }
+}
+
+impl ops::Mul<Point> for f64 {
+    type Output = Point;
+
+    fn mul(self, rhs: Point) -> Point {
+        rhs.scale(self)
+    }
+}
+
+impl<T: Into<Point>> ops::Div<T> for Point {
+    type Output = Self;
+
+    fn div(self, rhs: T) -> Self {
+        let rhs = rhs.into();
+        (self * rhs.conj()) / rhs.abs_sq()
+    }
+}
+
+impl ops::Div<f64> for Point {
+    type Output = Self;
+
+    fn div(self, rhs: f64) -> Self {
+        self.scale(rhs.recip())
+    }
+}
+
 impl ops::Neg for Point {
     type Output = Self;
 
@@ -98,4 +169,16 @@ impl<T: Into<Point> + Copy> cmp::PartialEq<T> for Point {
         let rhs: Point = (*rhs).into();
         self.x == rhs.x && self.y == rhs.y
     }
-}
\ No newline at end of file
+}
+
+impl iter::Sum for Point {
+    fn sum<I: Iterator<Item = Point>>(iter: I) -> Self {
+        iter.fold(Point::ZERO, |total, point| total + point)
+    }
+}
+
+impl<'a> iter::Sum<&'a Point> for Point {
+    fn sum<I: Iterator<Item = &'a Point>>(iter: I) -> Self {
+        iter.fold(Point::ZERO, |total, &point| total + point)
+    }
+}
@@ -0,0 +1,367 @@
+use std::collections::HashMap;
+use std::convert::TryFrom;
+use std::error::Error;
+use std::fmt;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+
+use gif::{Encoder, EncodingError, Frame, Repeat};
+use svg::node::element::path::Data;
+use svg::node::element::{Circle, Group, Line, Path as SvgPathElement};
+use svg::Document;
+
+use super::color::Color;
+use super::common::Point;
+use super::raster::RasterRenderer;
+use super::renderer::{Camera, Renderer};
+use super::world::World;
+use super::FourierSeries;
+
+#[derive(Debug)]
+pub enum ExportError {
+    Io(io::Error),
+    GifError(EncodingError),
+    PngError(png::EncodingError),
+    ImageTooLarge(u32, u32),
+    NoFrames,
+    RenderError(String)
+}
+
+impl From<io::Error> for ExportError {
+    fn from(value: io::Error) -> Self {
+        ExportError::Io(value)
+    }
+}
+
+impl From<EncodingError> for ExportError {
+    fn from(value: EncodingError) -> Self {
+        ExportError::GifError(value)
+    }
+}
+
+impl From<png::EncodingError> for ExportError {
+    fn from(value: png::EncodingError) -> Self {
+        ExportError::PngError(value)
+    }
+}
+
+impl From<String> for ExportError {
+    fn from(value: String) -> Self {
+        ExportError::RenderError(value)
+    }
+}
+
+impl fmt::Display for ExportError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ExportError::Io(err) => write!(f, "{}", err),
+            ExportError::GifError(err) => write!(f, "could not encode the GIF: {}", err),
+            ExportError::PngError(err) => write!(f, "could not encode the PNG: {}", err),
+            ExportError::ImageTooLarge(width, height) => write!(f, "{}x{} is too large for a GIF", width, height),
+            ExportError::NoFrames => write!(f, "there are no frames to export"),
+            ExportError::RenderError(err) => write!(f, "could not render a frame: {}", err)
+        }
+    }
+}
+
+impl Error for ExportError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ExportError::Io(err) => Some(err),
+            ExportError::GifError(err) => Some(err),
+            ExportError::PngError(err) => Some(err),
+            _ => None
+        }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ExportOptions {
+    // Frames in one full period for GIFs, or in total for frame sequences
+    pub frames: usize,
+    pub frame_rate: f64,
+    // Centre the camera on the drawing instead of keeping the renderer's camera
+    pub fit_camera: bool
+}
+
+impl Default for ExportOptions {
+    fn default() -> Self {
+        Self {
+            frames: 100,
+            frame_rate: 25.0,
+            fit_camera: true
+        }
+    }
+}
+
+// Renders one period into the renderer, calling `on_frame` after each frame is drawn.
+// The trail is filled in by a full period beforehand so the first and last frames line up.
+pub fn render_period<F>(fs: &mut FourierSeries<RasterRenderer>, options: ExportOptions, mut on_frame: F) -> Result<(), ExportError>
+    where F: FnMut(&RasterRenderer) -> Result<(), ExportError> {
+    if options.frames == 0 {
+        return Err(ExportError::NoFrames);
+    }
+
+    if options.fit_camera {
+        fit_camera(fs);
+    }
+
+    let substeps = (fs.get_trail_length() / options.frames).max(1);
+    let steps = options.frames * substeps;
+    fs.clear_draw_points();
+    for step in 0..steps {
+        let t = step as f64 / steps as f64 - 1.0;
+        let tip = fs.get_world().tip(t);
+        fs.add_draw_point(t, tip);
+    }
+
+    for frame in 0..options.frames {
+        if frame > 0 {
+            for substep in 1..substeps {
+                let t = ((frame - 1) * substeps + substep) as f64 / steps as f64;
+                let tip = fs.get_world().tip(t);
+                fs.add_draw_point(t, tip);
+            }
+        }
+        fs.draw_frame(frame as f64 / options.frames as f64)?;
+        on_frame(fs.get_renderer())?;
+    }
+
+    Ok(())
+}
+
+// Renders `frames` frames at simulated times frame / frame_rate * time_scale, so the
+// output doesn't depend on how fast the frames are rendered
+pub fn render_sequence<F>(fs: &mut FourierSeries<RasterRenderer>, options: ExportOptions, mut on_frame: F) -> Result<(), ExportError>
+    where F: FnMut(usize, &RasterRenderer) -> Result<(), ExportError> {
+    if options.frames == 0 {
+        return Err(ExportError::NoFrames);
+    }
+
+    if options.fit_camera {
+        fit_camera(fs);
+    }
+
+    fs.clear_draw_points();
+    for frame in 0..options.frames {
+        fs.draw_frame(frame as f64 / options.frame_rate * fs.get_time_scale())?;
+        on_frame(frame, fs.get_renderer())?;
+    }
+
+    Ok(())
+}
+
+// Writes frame_00000.png, frame_00001.png, ... into `directory`, creating it if needed
+pub fn export_png_frames<P: AsRef<Path>>(fs: &mut FourierSeries<RasterRenderer>, directory: P, options: ExportOptions) -> Result<(), ExportError> {
+    let directory = directory.as_ref();
+    fs::create_dir_all(directory)?;
+
+    render_sequence(fs, options, |frame, renderer| {
+        let path = directory.join(format!("frame_{:05}.png", frame));
+        write_png(renderer, BufWriter::new(File::create(path)?))
+    })
+}
+
+// Writes every frame as packed RGB24, e.g. for
+// `ffmpeg -f rawvideo -pixel_format rgb24 -video_size WxH -framerate FPS -i - out.mp4`
+pub fn export_raw_frames<W: Write>(fs: &mut FourierSeries<RasterRenderer>, mut writer: W, options: ExportOptions) -> Result<(), ExportError> {
+    let mut rgb = Vec::new();
+
+    render_sequence(fs, options, |_, renderer| {
+        rgb.clear();
+        rgb.extend(renderer.get_pixels().chunks_exact(4).flat_map(|pixel| pixel[..3].iter().cloned()));
+        writer.write_all(&rgb)?;
+        Ok(())
+    })?;
+
+    writer.flush()?;
+    Ok(())
+}
+
+pub fn write_png<W: Write>(renderer: &RasterRenderer, writer: W) -> Result<(), ExportError> {
+    let mut encoder = png::Encoder::new(writer, renderer.get_width(), renderer.get_height());
+    encoder.set_color(png::ColorType::Rgba);
+    encoder.set_depth(png::BitDepth::Eight);
+
+    let mut writer = encoder.write_header()?;
+    writer.write_image_data(renderer.get_pixels())?;
+    writer.finish()?;
+    Ok(())
+}
+
+pub fn export_gif<W: Write>(fs: &mut FourierSeries<RasterRenderer>, writer: W, options: ExportOptions) -> Result<(), ExportError> {
+    let (width, height) = fs.get_renderer().get_size();
+    let (gif_width, gif_height) = match (u16::try_from(width), u16::try_from(height)) {
+        (Ok(gif_width), Ok(gif_height)) => (gif_width, gif_height),
+        _ => return Err(ExportError::ImageTooLarge(width, height))
+    };
+    // GIF delays are in hundredths of a second
+    let delay = (100.0 / options.frame_rate).round().max(1.0) as u16;
+
+    let mut encoder = Encoder::new(writer, gif_width, gif_height, &[])?;
+    encoder.set_repeat(Repeat::Infinite)?;
+
+    render_period(fs, options, |renderer| {
+        let mut frame = quantize(renderer.get_pixels(), gif_width, gif_height);
+        frame.delay = delay;
+        encoder.write_frame(&frame)?;
+        Ok(())
+    })
+}
+
+fn fit_camera(fs: &mut FourierSeries<RasterRenderer>) {
+    let size = fs.get_renderer().get_size();
+    let camera = Camera::fit(&fs.get_world().sample_path(1000), size, size.0.min(size.1) as f64 / 20.0);
+    *fs.get_renderer_mut().get_camera_mut() = camera;
+}
+
+// The raster renderer only draws in the colours it's given, so frames almost always fit
+// in a palette exactly. Anything more colourful falls back to NeuQuant.
+fn quantize(pixels: &[u8], width: u16, height: u16) -> Frame<'static> {
+    let mut palette: Vec<u8> = Vec::new();
+    let mut indices: HashMap<[u8; 3], u8> = HashMap::new();
+    let mut indexed = Vec::with_capacity(pixels.len() / 4);
+
+    for pixel in pixels.chunks_exact(4) {
+        let color = [pixel[0], pixel[1], pixel[2]];
+        let index = match indices.get(&color) {
+            Some(&index) => index,
+            None if indices.len() < 256 => {
+                let index = indices.len() as u8;
+                indices.insert(color, index);
+                palette.extend_from_slice(&color);
+                index
+            },
+            None => return Frame::from_rgba_speed(width, height, &mut pixels.to_vec(), 10)
+        };
+        indexed.push(index);
+    }
+
+    Frame::from_palette_pixels(width, height, &indexed, &palette, None)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct SvgExportOptions {
+    // Points sampled along the reconstructed path
+    pub samples: usize,
+    // Also draw the vectors and their circles at this time
+    pub epicycles_at: Option<f64>,
+    pub path_color: Color,
+    pub svector_color: Color,
+    pub circle_color: Color,
+    // In units of the output's longest side, so it doesn't depend on the drawing's scale
+    pub stroke_width: f64,
+    // The world is y up, so flipping puts the drawing back the way round it was in the input SVG
+    pub flip_y: bool
+}
+
+impl Default for SvgExportOptions {
+    fn default() -> Self {
+        Self {
+            samples: 1000,
+            epicycles_at: None,
+            path_color: Color::RGB(0, 0, 255),
+            svector_color: Color::RGB(0, 0, 0),
+            circle_color: Color::RGB(160, 160, 160),
+            stroke_width: 0.002,
+            flip_y: true
+        }
+    }
+}
+
+fn svg_color(color: Color) -> String {
+    format!("#{:02x}{:02x}{:02x}", color.r, color.g, color.b)
+}
+
+pub fn svg_document(world: &World, options: SvgExportOptions) -> Document {
+    let flip = |p: Point| if options.flip_y { p.conj() } else { p };
+
+    let points: Vec<Point> = world.sample_path(options.samples.max(1)).into_iter().map(flip).collect();
+
+    // (start, vector) for every arm
+    let arms: Vec<(Point, Point)> = match options.epicycles_at {
+        Some(t) => {
+            let mut start = Point::ZERO;
+            world.get_state(t).map(|vector| {
+                let arm = (flip(start), flip(vector));
+                start += vector;
+                arm
+            }).collect()
+        },
+        None => Vec::new()
+    };
+
+    let mut extent = points.clone();
+    for &(start, vector) in arms.iter() {
+        let radius = vector.mag();
+        extent.push(start - (radius, radius));
+        extent.push(start + (radius, radius));
+    }
+    let (min, max) = extent.iter().fold(
+        (Point::new(f64::INFINITY, f64::INFINITY), Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+        |(min, max), p| (Point::new(min.x.min(p.x), min.y.min(p.y)), Point::new(max.x.max(p.x), max.y.max(p.y)))
+    );
+    let size = max - min;
+    let longest = size.x.max(size.y).max(f64::EPSILON);
+    let margin = longest * 0.05;
+    let stroke_width = longest * options.stroke_width;
+
+    let mut data = Data::new();
+    if let Some((first, rest)) = points.split_first() {
+        data = data.move_to((first.x, first.y));
+        for point in rest {
+            data = data.line_to((point.x, point.y));
+        }
+        data = data.close();
+    }
+
+    let path = SvgPathElement::new()
+        .set("fill", "none")
+        .set("stroke", svg_color(options.path_color))
+        .set("stroke-width", stroke_width)
+        .set("d", data);
+
+    let mut document = Document::new()
+        .set("viewBox", (min.x - margin, min.y - margin, size.x + 2.0 * margin, size.y + 2.0 * margin))
+        .add(path);
+
+    if !arms.is_empty() {
+        let mut circles = Group::new()
+            .set("fill", "none")
+            .set("stroke", svg_color(options.circle_color))
+            .set("stroke-width", stroke_width / 2.0);
+        let mut lines = Group::new()
+            .set("stroke", svg_color(options.svector_color))
+            .set("stroke-width", stroke_width);
+
+        for (&(start, vector), svector) in arms.iter().zip(world.get_svectors()) {
+            if svector.get_frequency() != 0.0 {
+                circles = circles.add(Circle::new()
+                    .set("cx", start.x)
+                    .set("cy", start.y)
+                    .set("r", vector.mag()));
+            }
+            let end = start + vector;
+            lines = lines.add(Line::new()
+                .set("x1", start.x)
+                .set("y1", start.y)
+                .set("x2", end.x)
+                .set("y2", end.y));
+        }
+
+        document = document.add(circles).add(lines);
+    }
+
+    document
+}
+
+pub fn write_svg<W: Write>(world: &World, writer: W, options: SvgExportOptions) -> Result<(), ExportError> {
+    svg::write(writer, &svg_document(world, options))?;
+    Ok(())
+}
+
+pub fn export_svg<P: AsRef<Path>>(world: &World, path: P, options: SvgExportOptions) -> Result<(), ExportError> {
+    svg::save(path, &svg_document(world, options))?;
+    Ok(())
+}
@@ -0,0 +1,177 @@
+use serde::{Deserialize, Serialize};
+
+use super::common::Point;
+use super::maths::fft;
+use super::path::{sampled_fourier_coefficient, ParametricPath};
+use super::world::{SVector, World};
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum CoefficientOrder {
+    // 1, -1, 2, -2, ...
+    Frequency,
+    // Largest magnitude first
+    Magnitude
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum CoefficientMethod {
+    // Averages the samples separately for every frequency
+    Average,
+    // Transforms one set of samples for all frequencies at once
+    Fft,
+    // Uses ParametricPath::fourier_coefficient, which is exact for lines and Bézier curves.
+    // SVGPath only integrates exactly with Parameterization::SegmentIndex and samples otherwise
+    Exact
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub enum TermSelection {
+    All,
+    // The given number of largest rotating terms, the constant term is kept as well
+    Count(usize),
+    // Fewest rotating terms holding this fraction of their total squared magnitude
+    EnergyFraction(f64),
+    // Fewest terms whose reconstruction has at most this RMS error
+    RmsError(f64)
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FourierConfig {
+    pub method: CoefficientMethod,
+    pub include_dc: bool,
+    pub max_frequency: u32,
+    pub samples: usize,
+    pub order: CoefficientOrder,
+    pub terms: TermSelection
+}
+
+impl Default for FourierConfig {
+    fn default() -> Self {
+        Self {
+            method: CoefficientMethod::Fft,
+            include_dc: true,
+            max_frequency: 99,
+            samples: 10000,
+            order: CoefficientOrder::Frequency,
+            terms: TermSelection::All
+        }
+    }
+}
+
+pub fn coefficient(path: &dyn ParametricPath, frequency: i32, samples: usize) -> Point {
+    sampled_fourier_coefficient(path, frequency as f64, samples)
+}
+
+pub fn compute_coefficients(path: &dyn ParametricPath, config: FourierConfig) -> Vec<SVector> {
+    let spectrum = match config.method {
+        CoefficientMethod::Fft => {
+            let points: Vec<Point> = (0..config.samples)
+                .map(|x| path.get_point(x as f64 / config.samples as f64))
+                .collect();
+            Some(fft(&points))
+        },
+        _ => None
+    };
+
+    let get_coefficient = |freq: i32| match (config.method, &spectrum) {
+        (_, Some(spectrum)) => spectrum[freq.rem_euclid(config.samples as i32) as usize] / config.samples as f64,
+        (CoefficientMethod::Exact, None) => path.fourier_coefficient(freq as f64, config.samples),
+        _ => coefficient(path, freq, config.samples)
+    };
+
+    let mut svectors: Vec<SVector> = Vec::new();
+
+    // The constant term anchors the rest of the vectors at the path's centroid
+    if config.include_dc {
+        let centroid = get_coefficient(0);
+        svectors.push(SVector::new(centroid.angle(), 0.0, centroid.mag()));
+    }
+
+    for freq in 1..=config.max_frequency as i32 {
+        for &freq in [freq, -freq].iter() {
+            let point_avg = get_coefficient(freq);
+
+            svectors.push(SVector::new(
+                point_avg.angle(),
+                freq as f64,
+                point_avg.mag()
+            ));
+        }
+    }
+
+    let skip = if config.include_dc { 1 } else { 0 };
+
+    if config.terms != TermSelection::All {
+        // The constant term is always kept, so only the rotating terms are selected from
+        let (constant, rotating) = svectors.split_at_mut(skip);
+        rotating.sort_by(|a, b| b.get_magnitude().total_cmp(&a.get_magnitude()));
+        let center = constant.first().map_or(Point::ZERO, |svector| svector.get_state(0.0));
+        let count = select_term_count(path, center, rotating, config);
+        svectors.truncate(skip + count);
+
+        if config.order == CoefficientOrder::Frequency {
+            svectors[skip..].sort_by_key(|svector| {
+                let freq = svector.get_frequency() as i64;
+                (freq.abs(), freq < 0)
+            });
+        }
+    } else if config.order == CoefficientOrder::Magnitude {
+        svectors[skip..].sort_by(|a, b| b.get_magnitude().total_cmp(&a.get_magnitude()));
+    }
+
+    svectors
+}
+
+// Expects the rotating vectors largest first. By Parseval's theorem the squared RMS error of a
+// truncated series is the path's energy about its constant term minus the energy of the kept terms.
+fn select_term_count(path: &dyn ParametricPath, center: Point, svectors: &[SVector], config: FourierConfig) -> usize {
+    let energies: Vec<f64> = svectors.iter().map(|svector| svector.get_magnitude().powi(2)).collect();
+
+    let remaining_allowed = match config.terms {
+        TermSelection::All => return svectors.len(),
+        TermSelection::Count(count) => return count.min(svectors.len()),
+        TermSelection::EnergyFraction(fraction) => {
+            let total: f64 = energies.iter().sum();
+            total * (1.0 - fraction)
+        },
+        TermSelection::RmsError(error) => {
+            let path_energy = (0..config.samples)
+                .map(|x| (path.get_point(x as f64 / config.samples as f64) - center).mag().powi(2))
+                .sum::<f64>() / config.samples as f64;
+            let total: f64 = energies.iter().sum();
+            total - (path_energy - error.powi(2))
+        }
+    };
+
+    let mut remaining: f64 = energies.iter().sum();
+    for (count, energy) in energies.iter().enumerate() {
+        if remaining <= remaining_allowed {
+            return count;
+        }
+        remaining -= energy;
+    }
+
+    svectors.len()
+}
+
+pub fn rms_error(path: &dyn ParametricPath, world: &World, samples: usize) -> f64 {
+    let total: f64 = (0..samples)
+        .map(|x| x as f64 / samples as f64)
+        .map(|t| (path.get_point(t) - world.tip(t)).mag().powi(2))
+        .sum();
+
+    (total / samples as f64).sqrt()
+}
+
+pub fn hausdorff_error(path: &dyn ParametricPath, world: &World, samples: usize) -> f64 {
+    let original: Vec<Point> = (0..samples)
+        .map(|x| path.get_point(x as f64 / samples as f64))
+        .collect();
+    let reconstruction = world.sample_path(samples);
+
+    let directed = |from: &[Point], to: &[Point]| from.iter()
+        .map(|&p| to.iter().map(|&q| (p - q).mag()).fold(f64::INFINITY, f64::min))
+        .fold(0.0, f64::max);
+
+    directed(&original, &reconstruction).max(directed(&reconstruction, &original))
+}
@@ -1,187 +1,606 @@
 use std::collections::VecDeque;
-use std::f64::consts::PI;
-use std::iter::FromIterator;
+use std::error::Error;
+use std::fmt;
+use std::path::PathBuf;
+#[cfg(feature = "sdl")]
 use std::time::{Duration, Instant};
 
+pub mod clock;
+pub mod coefficients;
+pub mod color;
 pub mod common;
+#[cfg(feature = "sdl")]
 pub mod canvas;
+pub mod export;
+pub mod fourier;
 pub mod maths;
 pub mod path;
+pub mod raster;
+pub mod renderer;
 pub mod svgpath;
+pub mod transform;
 pub mod world;
 
-use world::{SVector, World};
-use canvas::{Canvas, Color, Event, Keycode};
+use world::World;
+#[cfg(feature = "sdl")]
+use canvas::{Canvas, CanvasBuildError, Event, Keycode};
+use color::Color;
+use clock::SimulationClock;
+use coefficients::{CoefficientFile, CoefficientFileError, CoefficientFormat, CoefficientMetadata};
 use common::Point;
-use maths::point_average;
-use path::ParametricPath;
-use svgpath::SVGPath;
+use fourier::{compute_coefficients, CoefficientMethod, CoefficientOrder, FourierConfig, TermSelection};
+use renderer::Renderer;
+use svgpath::{Normalization, Parameterization, SVGPath, SvgLoadError};
+
+#[derive(Debug, PartialEq)]
+pub enum ConfigError {
+    InvalidWindowSize(u32, u32),
+    InvalidTimeScale(f64),
+    InvalidFrameRate(f64),
+    NoSamples,
+    // Fewer than two samples per period of the highest frequency
+    TooFewSamples(usize, u32),
+    // Exact coefficients need the SVG's own segment parameterization
+    ExactWithArcLength,
+    InvalidTermSelection(TermSelection)
+}
 
-pub struct FourierSeries {
-    canvas: canvas::Canvas,
-    world: world::World,
-    point_path: VecDeque<Point>,
-    svector_color: Color,
-    point_path_color: Color,
-    background: Color,
-    start_time: Instant,
-    time_scale: f64,
-    frame_rate: f64,
-    camera_lock: bool,
-    running: bool
+#[derive(Debug)]
+pub enum FourierSeriesError {
+    #[cfg(feature = "sdl")]
+    CanvasBuildError(CanvasBuildError),
+    SvgLoadError(SvgLoadError),
+    CoefficientFileError(CoefficientFileError),
+    ConfigError(ConfigError)
 }
 
-impl FourierSeries {
-    pub fn new() -> Self {
-        let canvas = Canvas::new("Test Window", 800, 600).unwrap();
-        let p = SVGPath::new("example.svg").unwrap();
+#[cfg(feature = "sdl")]
+impl From<CanvasBuildError> for FourierSeriesError {
+    fn from(value: CanvasBuildError) -> Self {
+        FourierSeriesError::CanvasBuildError(value)
+    }
+}
 
-        let mut svectors: Vec<SVector> = Vec::new();
+impl From<SvgLoadError> for FourierSeriesError {
+    fn from(value: SvgLoadError) -> Self {
+        FourierSeriesError::SvgLoadError(value)
+    }
+}
 
-        for freq in 1..100 {
-            let points = (0..10000)
-                .map(|x| x as f64/10000.0)
-                .map(|x| Point::complex_mult(p.get_point(x), Point::from_ei(-freq as f64 * 2.0 * PI * x)));
+impl From<CoefficientFileError> for FourierSeriesError {
+    fn from(value: CoefficientFileError) -> Self {
+        FourierSeriesError::CoefficientFileError(value)
+    }
+}
 
-            let point_avg = point_average(points);
+impl From<ConfigError> for FourierSeriesError {
+    fn from(value: ConfigError) -> Self {
+        FourierSeriesError::ConfigError(value)
+    }
+}
 
-            svectors.push(SVector::new(
-                point_avg.angle(),
-                freq as f64,
-                point_avg.mag()
-            ));
+impl fmt::Display for ConfigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ConfigError::InvalidWindowSize(width, height) => write!(f, "invalid window size: {}x{}", width, height),
+            ConfigError::InvalidTimeScale(time_scale) => write!(f, "time scale must be positive: {}", time_scale),
+            ConfigError::InvalidFrameRate(frame_rate) => write!(f, "frame rate must be positive: {}", frame_rate),
+            ConfigError::NoSamples => write!(f, "at least one sample is needed"),
+            ConfigError::TooFewSamples(samples, max_frequency) => write!(
+                f, "{} samples cannot resolve frequency {}, use more than {}",
+                samples, max_frequency, 2 * *max_frequency as u64
+            ),
+            ConfigError::ExactWithArcLength => {
+                write!(f, "exact coefficients need the segment index parameterization")
+            },
+            ConfigError::InvalidTermSelection(terms) => write!(f, "invalid term selection: {:?}", terms)
+        }
+    }
+}
 
-            let points = (0..10000)
-                .map(|x| x as f64/10000.0)
-                .map(|x| Point::complex_mult(p.get_point(x), Point::from_ei(freq as f64 * 2.0 * PI * x)));
+impl Error for ConfigError {}
 
-            let point_avg = point_average(points);
+impl fmt::Display for FourierSeriesError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            #[cfg(feature = "sdl")]
+            FourierSeriesError::CanvasBuildError(err) => write!(f, "could not open the window: {}", err),
+            FourierSeriesError::SvgLoadError(err) => write!(f, "{}", err),
+            FourierSeriesError::CoefficientFileError(err) => write!(f, "{}", err),
+            FourierSeriesError::ConfigError(err) => write!(f, "{}", err)
+        }
+    }
+}
 
-            svectors.push(SVector::new(
-                point_avg.angle(),
-                -freq as f64,
-                point_avg.mag()
-            ));
+impl Error for FourierSeriesError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            #[cfg(feature = "sdl")]
+            FourierSeriesError::CanvasBuildError(err) => Some(err),
+            FourierSeriesError::SvgLoadError(err) => Some(err),
+            FourierSeriesError::CoefficientFileError(err) => Some(err),
+            FourierSeriesError::ConfigError(err) => Some(err)
         }
-        
-        let world = World::new(svectors);
+    }
+}
+
+#[derive(Clone, Debug)]
+pub struct FourierSeriesConfig {
+    // An SVG, or a .json/.csv file of saved coefficients
+    pub input: PathBuf,
+    pub title: String,
+    pub width: u32,
+    pub height: u32,
+    pub fourier: FourierConfig,
+    pub parameterization: Parameterization,
+    pub normalization: Normalization,
+    pub time_scale: f64,
+    pub frame_rate: f64,
+    pub svector_color: Color,
+    pub point_path_color: Color,
+    pub background: Color,
+    pub trail_length: usize,
+    pub start_paused: bool
+}
 
+impl Default for FourierSeriesConfig {
+    fn default() -> Self {
         Self {
-            canvas, world, point_path: VecDeque::new(),
+            input: PathBuf::from("example.svg"),
+            title: String::from("Fourier Series"),
+            width: 800,
+            height: 600,
+            fourier: FourierConfig::default(),
+            parameterization: Parameterization::ArcLength,
+            normalization: Normalization::default(),
+            time_scale: 0.1,
+            frame_rate: 60.0,
             svector_color: Color::RGB(255, 255, 255),
             point_path_color: Color::RGB(0, 0, 255),
             background: Color::RGB(0, 0, 0),
-            time_scale: 0.1,
-            start_time: Instant::now(),
-            frame_rate: 60.0,
-            camera_lock: false,
-            running: false
+            trail_length: 1000,
+            start_paused: false
         }
     }
+}
 
-    pub fn get_background(&self) -> Color {
-        self.background
+impl FourierSeriesConfig {
+    pub fn validate(&self) -> Result<(), ConfigError> {
+        if self.width == 0 || self.height == 0 {
+            return Err(ConfigError::InvalidWindowSize(self.width, self.height));
+        }
+        if !self.time_scale.is_finite() || self.time_scale <= 0.0 {
+            return Err(ConfigError::InvalidTimeScale(self.time_scale));
+        }
+        if !self.frame_rate.is_finite() || self.frame_rate <= 0.0 {
+            return Err(ConfigError::InvalidFrameRate(self.frame_rate));
+        }
+        // Saved coefficients are used as they are, so the Fourier options don't apply
+        if CoefficientFormat::from_path(&self.input).is_some() {
+            return Ok(());
+        }
+        if self.fourier.samples == 0 {
+            return Err(ConfigError::NoSamples);
+        }
+        if self.fourier.samples as u64 <= 2 * self.fourier.max_frequency as u64 {
+            return Err(ConfigError::TooFewSamples(self.fourier.samples, self.fourier.max_frequency));
+        }
+        if self.fourier.method == CoefficientMethod::Exact && self.parameterization == Parameterization::ArcLength {
+            return Err(ConfigError::ExactWithArcLength);
+        }
+        match self.fourier.terms {
+            TermSelection::Count(0) => Err(ConfigError::InvalidTermSelection(self.fourier.terms)),
+            TermSelection::EnergyFraction(fraction) if !(fraction > 0.0 && fraction <= 1.0) => {
+                Err(ConfigError::InvalidTermSelection(self.fourier.terms))
+            },
+            TermSelection::RmsError(error) if error.is_nan() || error < 0.0 => {
+                Err(ConfigError::InvalidTermSelection(self.fourier.terms))
+            },
+            _ => Ok(())
+        }
     }
+}
 
-    pub fn get_start_time(&self) -> Instant {
-        self.start_time
+#[derive(Clone, Debug, Default)]
+pub struct FourierSeriesBuilder {
+    config: FourierSeriesConfig
+}
+
+impl FourierSeriesBuilder {
+    pub fn new() -> Self {
+        Self::default()
     }
 
-    pub fn reset_start_time(&mut self) {
-        self.start_time = Instant::now();
-    } 
+    pub fn source<P: Into<PathBuf>>(mut self, path: P) -> Self {
+        self.config.input = path.into();
+        self
+    }
 
-    pub fn get_time_scale(&self) -> f64 {
-        self.time_scale
+    pub fn title<S: Into<String>>(mut self, title: S) -> Self {
+        self.config.title = title.into();
+        self
     }
 
-    pub fn get_frame_rate(&self) -> f64 {
-        self.frame_rate
+    pub fn window_size(mut self, width: u32, height: u32) -> Self {
+        self.config.width = width;
+        self.config.height = height;
+        self
     }
 
-    pub fn set_running(&mut self, value: bool) {
-        self.running = value;
+    pub fn fourier_config(mut self, fourier: FourierConfig) -> Self {
+        self.config.fourier = fourier;
+        self
     }
 
-    pub fn get_running(&self) -> bool {
-        self.running
+    pub fn coefficient_method(mut self, method: CoefficientMethod) -> Self {
+        self.config.fourier.method = method;
+        self
     }
-    
-    pub fn present_canvas(&mut self) {
-        self.canvas.present();
+
+    pub fn max_frequency(mut self, max_frequency: u32) -> Self {
+        self.config.fourier.max_frequency = max_frequency;
+        self
     }
 
-    pub fn clear_canvas(&mut self) {
-        self.canvas.clear();
+    pub fn samples(mut self, samples: usize) -> Self {
+        self.config.fourier.samples = samples;
+        self
     }
 
-    pub fn set_canvas_draw_color<T: Into<Color>>(&mut self, color: T) {
-        self.canvas.set_draw_color(color);
+    pub fn order(mut self, order: CoefficientOrder) -> Self {
+        self.config.fourier.order = order;
+        self
+    }
+
+    pub fn terms(mut self, terms: TermSelection) -> Self {
+        self.config.fourier.terms = terms;
+        self
+    }
+
+    pub fn parameterization(mut self, parameterization: Parameterization) -> Self {
+        self.config.parameterization = parameterization;
+        self
+    }
+
+    pub fn normalization(mut self, normalization: Normalization) -> Self {
+        self.config.normalization = normalization;
+        self
+    }
+
+    pub fn time_scale(mut self, time_scale: f64) -> Self {
+        self.config.time_scale = time_scale;
+        self
+    }
+
+    pub fn frame_rate(mut self, frame_rate: f64) -> Self {
+        self.config.frame_rate = frame_rate;
+        self
+    }
+
+    pub fn svector_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.config.svector_color = color.into();
+        self
+    }
+
+    pub fn point_path_color<T: Into<Color>>(mut self, color: T) -> Self {
+        self.config.point_path_color = color.into();
+        self
+    }
+
+    pub fn background<T: Into<Color>>(mut self, color: T) -> Self {
+        self.config.background = color.into();
+        self
+    }
+
+    pub fn trail_length(mut self, trail_length: usize) -> Self {
+        self.config.trail_length = trail_length;
+        self
+    }
+
+    pub fn start_paused(mut self, value: bool) -> Self {
+        self.config.start_paused = value;
+        self
+    }
+
+    pub fn get_config(&self) -> &FourierSeriesConfig {
+        &self.config
+    }
+
+    #[cfg(feature = "sdl")]
+    pub fn build(self) -> Result<FourierSeries, FourierSeriesError> {
+        FourierSeries::with_config(self.config)
+    }
+
+    pub fn build_with_renderer<R: Renderer>(self, renderer: R) -> Result<FourierSeries<R>, FourierSeriesError> {
+        FourierSeries::with_renderer(self.config, renderer)
+    }
+}
+
+impl From<FourierSeriesConfig> for FourierSeriesBuilder {
+    fn from(config: FourierSeriesConfig) -> Self {
+        Self { config }
+    }
+}
+
+// Reads the coefficients if the input is a coefficient file, otherwise computes them from the SVG
+pub fn load_coefficients(config: &FourierSeriesConfig) -> Result<CoefficientFile, FourierSeriesError> {
+    if CoefficientFormat::from_path(&config.input).is_some() {
+        return Ok(CoefficientFile::open(&config.input)?);
+    }
+    config.validate()?;
+
+    let mut p = SVGPath::new(&config.input)?;
+    p.set_parameterization(config.parameterization);
+    p.normalize(config.normalization);
+
+    let svectors = compute_coefficients(&p, config.fourier);
+
+    let metadata = CoefficientMetadata {
+        source: Some(config.input.display().to_string()),
+        samples: Some(config.fourier.samples),
+        max_frequency: Some(config.fourier.max_frequency),
+        method: Some(config.fourier.method),
+        parameterization: Some(config.parameterization),
+        normalization: Some(config.normalization)
+    };
+
+    Ok(CoefficientFile::new(metadata, &svectors))
+}
+
+// Without the sdl feature there is no window, so frames can only be rendered offscreen
+#[cfg(feature = "sdl")]
+type DefaultRenderer = Canvas;
+#[cfg(not(feature = "sdl"))]
+type DefaultRenderer = raster::RasterRenderer;
+
+pub struct FourierSeries<R: Renderer = DefaultRenderer> {
+    renderer: R,
+    world: world::World,
+    // Tip positions with the time they were drawn at
+    point_path: VecDeque<(f64, Point)>,
+    svector_color: Color,
+    point_path_color: Color,
+    background: Color,
+    clock: SimulationClock,
+    frame_rate: f64,
+    trail_length: usize,
+    camera_lock: bool,
+    running: bool
+}
+
+#[cfg(feature = "sdl")]
+impl FourierSeries {
+    pub fn new() -> Result<Self, FourierSeriesError> {
+        Self::with_config(FourierSeriesConfig::default())
+    }
+
+    pub fn builder() -> FourierSeriesBuilder {
+        FourierSeriesBuilder::new()
+    }
+
+    pub fn with_config(config: FourierSeriesConfig) -> Result<Self, FourierSeriesError> {
+        config.validate()?;
+
+        let canvas = Canvas::new(&config.title, config.width, config.height)?;
+        Self::with_renderer(config, canvas)
     }
 
     pub fn handle_events(&mut self) {
-        while let Some(event) = self.canvas.poll_event() {
+        while let Some(event) = self.renderer.poll_event() {
             match event {
                 Event::Quit {..} => { self.running = false; }
-                Event::MouseMotion {mousestate, xrel, yrel, ..} => {
-                    if mousestate.left() && !self.camera_lock {
-                        self.canvas.move_camera_by_pixels(xrel, yrel);
-                    }
+                Event::MouseMotion {mousestate, xrel, yrel, ..} if mousestate.left() && !self.camera_lock => {
+                    self.renderer.move_camera_by_pixels(xrel, yrel);
                 }
                 Event::MouseWheel {y, ..} => {
-                    let mousestate = self.canvas.mouse_state();
-                    self.canvas.zoom((1.2 as f64).powi(y), mousestate.x(), mousestate.y());
+                    let mousestate = self.renderer.mouse_state();
+                    self.renderer.zoom(1.2_f64.powi(y), mousestate.x(), mousestate.y());
                 }
-                Event::KeyDown { keycode, repeat, .. } => if repeat { match keycode {
-                    _ => {}
-                }} else { match keycode {
+                // Stepping repeats while the key is held
+                Event::KeyDown { keycode: Some(Keycode::Right), .. } => {
+                    self.clock.set_paused(true);
+                    self.clock.step(self.frame_rate.recip());
+                },
+                Event::KeyDown { keycode: Some(Keycode::Left), .. } => {
+                    self.clock.set_paused(true);
+                    self.clock.step(-self.frame_rate.recip());
+                },
+                Event::KeyDown { keycode, repeat: false, .. } => match keycode {
                     Some(Keycode::Equals) => {
-                        self.time_scale *= 1.2;
+                        let time_scale = self.clock.get_time_scale();
+                        self.clock.set_time_scale(time_scale * 1.2);
                     },
                     Some(Keycode::Minus) => {
-                        self.time_scale /= 1.2;
+                        let time_scale = self.clock.get_time_scale();
+                        self.clock.set_time_scale(time_scale / 1.2);
                     },
                     Some(Keycode::R) => {
-                        self.point_path.clear();
-                        self.start_time = Instant::now();
+                        self.seek(0.0);
+                    },
+                    Some(Keycode::Space) => {
+                        self.clock.toggle_paused();
                     },
                     Some(Keycode::L) => {
                         self.camera_lock = !self.camera_lock;
                     },
                     _ => {}
-                }},
+                },
                 _ => {}
             }
         }
     }
 
+    pub fn mainloop(&mut self) {
+        self.running = true;
+
+        let mut last_frame = Instant::now();
+
+        while self.running {
+            let now = Instant::now();
+            self.handle_events();
+            let time = self.clock.advance((now - last_frame).as_secs_f64());
+            last_frame = now;
+
+            self.draw_frame(time).unwrap();
+
+            let framedelay = Duration::from_secs_f64(1.0 / self.frame_rate);
+            let elapsed = now.elapsed();
+
+            if elapsed < framedelay {
+                ::std::thread::sleep(framedelay - elapsed);
+            }
+        };
+    }
+}
+
+impl<R: Renderer> FourierSeries<R> {
+    // Draws onto any renderer, e.g. a RasterRenderer when there is no display
+    pub fn with_renderer(config: FourierSeriesConfig, renderer: R) -> Result<Self, FourierSeriesError> {
+        config.validate()?;
+
+        let world = load_coefficients(&config)?.to_world();
+
+        let mut clock = SimulationClock::new(config.time_scale);
+        clock.set_paused(config.start_paused);
+
+        Ok(Self {
+            renderer, world, point_path: VecDeque::new(),
+            svector_color: config.svector_color,
+            point_path_color: config.point_path_color,
+            background: config.background,
+            clock,
+            frame_rate: config.frame_rate,
+            trail_length: config.trail_length,
+            camera_lock: false,
+            running: false
+        })
+    }
+
+    pub fn get_renderer(&self) -> &R {
+        &self.renderer
+    }
+
+    pub fn get_renderer_mut(&mut self) -> &mut R {
+        &mut self.renderer
+    }
+
+    pub fn get_world(&self) -> &World {
+        &self.world
+    }
+
+    pub fn get_background(&self) -> Color {
+        self.background
+    }
+
+    pub fn get_clock(&self) -> &SimulationClock {
+        &self.clock
+    }
+
+    pub fn get_clock_mut(&mut self) -> &mut SimulationClock {
+        &mut self.clock
+    }
+
+    pub fn get_time(&self) -> f64 {
+        self.clock.get_time()
+    }
+
+    // Jumps to time t, starting a new trail from there
+    pub fn seek(&mut self, t: f64) {
+        self.clock.seek(t);
+        self.point_path.clear();
+    }
+
+    pub fn set_time_scale(&mut self, time_scale: f64) {
+        self.clock.set_time_scale(time_scale);
+    }
+
+    pub fn get_time_scale(&self) -> f64 {
+        self.clock.get_time_scale()
+    }
+
+    pub fn get_frame_rate(&self) -> f64 {
+        self.frame_rate
+    }
+
+    pub fn get_trail_length(&self) -> usize {
+        self.trail_length
+    }
+
+    pub fn get_svector_color(&self) -> Color {
+        self.svector_color
+    }
+
+    pub fn get_point_path_color(&self) -> Color {
+        self.point_path_color
+    }
+
+    pub fn set_running(&mut self, value: bool) {
+        self.running = value;
+    }
+
+    pub fn get_running(&self) -> bool {
+        self.running
+    }
+
+    pub fn set_paused(&mut self, value: bool) {
+        self.clock.set_paused(value);
+    }
+
+    pub fn get_paused(&self) -> bool {
+        self.clock.get_paused()
+    }
+
+    pub fn set_camera_lock(&mut self, value: bool) {
+        self.camera_lock = value;
+    }
+
+    pub fn get_camera_lock(&self) -> bool {
+        self.camera_lock
+    }
+    
+    pub fn present_canvas(&mut self) {
+        self.renderer.present();
+    }
+
+    pub fn clear_canvas(&mut self) {
+        self.renderer.clear();
+    }
+
+    pub fn set_canvas_draw_color<T: Into<Color>>(&mut self, color: T) {
+        self.renderer.set_color(color.into());
+    }
+
     pub fn draw_svectors(&mut self, t: f64) -> Result<Point, String> {
-        self.canvas.set_draw_color(self.svector_color);
+        self.renderer.set_color(self.svector_color);
 
         let mut last_point = Point::new(0.0, 0.0);
         for point in self.world.get_state(t) {
-            self.canvas.draw_line(last_point, last_point+point)?;
+            self.renderer.draw_line(last_point, last_point+point)?;
             last_point += point;
         }
         Ok(last_point)
     }
 
-    pub fn add_draw_point(&mut self, point: Point) {
-        self.point_path.push_back(point);
+    pub fn add_draw_point(&mut self, t: f64, point: Point) {
+        self.point_path.push_back((t, point));
 
-        if self.point_path.len() > 1000 {
+        if self.point_path.len() > self.trail_length {
             self.point_path.pop_front();
         }
     }
 
+    pub fn clear_draw_points(&mut self) {
+        self.point_path.clear();
+    }
+
     pub fn draw_point_path(&mut self) -> Result<(), String> {
-        self.canvas.set_draw_color(self.point_path_color);
+        self.renderer.set_color(self.point_path_color);
 
-        let mut points = self.point_path.iter();
+        let mut points = self.point_path.iter().map(|(_, point)| point);
         if let Some(start_point) = points.next() {
             let mut last_point = *start_point;
             for point in points {
-                self.canvas.draw_line(last_point, *point)?;
+                self.renderer.draw_line(last_point, *point)?;
                 last_point = *point;
             }
             Ok(())
@@ -190,34 +609,24 @@ impl FourierSeries {
         }
     }
 
-    pub fn mainloop(&mut self) {
-        self.running = true;
-
-        self.start_time = Instant::now();
-
-        while self.running {
-            let now = Instant::now();
-            self.handle_events();
-            let time = self.start_time.elapsed().as_secs_f64() * self.time_scale;
-            println!("{}", time);
-
-            self.canvas.set_draw_color(self.background);
-            self.canvas.clear();
-
-            let tail = self.draw_svectors(time).unwrap();
-            if self.camera_lock {
-                self.canvas.set_camera_pos(tail);
-            }
-            self.add_draw_point(tail);
-            self.draw_point_path().unwrap();
-            self.canvas.present();
+    // Clears, draws the vectors at time t and the trail, and presents
+    pub fn draw_frame(&mut self, t: f64) -> Result<(), String> {
+        self.renderer.set_color(self.background);
+        self.renderer.clear();
 
-            let framedelay = Duration::new(0, 1_000_000_000u32 / self.frame_rate as u32);
-            let elapsed = now.elapsed();
-
-            if elapsed < framedelay {
-                ::std::thread::sleep(framedelay - elapsed);
-            }
-        };
+        let tail = self.draw_svectors(t)?;
+        if self.camera_lock {
+            self.renderer.set_camera_pos(tail);
+        }
+        // Going back in time (stepping or seeking backwards) rewinds the trail
+        while self.point_path.back().is_some_and(|&(last_t, _)| last_t > t) {
+            self.point_path.pop_back();
+        }
+        if self.point_path.back().is_none_or(|&(last_t, _)| last_t < t) {
+            self.add_draw_point(t, tail);
+        }
+        self.draw_point_path()?;
+        self.renderer.present();
+        Ok(())
     }
-}
\ No newline at end of file
+}
@@ -1,6 +1,328 @@
-use fourier_series::FourierSeries;
+use std::env;
+use std::fs::File;
+use std::io::{self, BufWriter};
+use std::path::PathBuf;
+use std::process;
+
+use fourier_series::color::Color;
+use fourier_series::export::{export_gif, export_png_frames, export_raw_frames, export_svg, ExportOptions, SvgExportOptions};
+use fourier_series::fourier::TermSelection;
+use fourier_series::raster::RasterRenderer;
+use fourier_series::{load_coefficients, FourierSeriesBuilder, FourierSeriesConfig};
+
+const USAGE: &str = "Usage: fourier-series [OPTIONS] [FILE]
+
+Draws FILE (default: example.svg) with rotating vectors. FILE can also be a .json or
+.csv file of coefficients saved with --save-coefficients.
+
+Options:
+    --size WxH              Window size (default: 800x600)
+    --title TITLE           Window title
+    --max-frequency N       Highest frequency computed (default: 99)
+    --terms N               Only keep the N largest rotating vectors
+    --samples N             Samples taken along the path (default: 10000)
+    --time-scale X          Periods per second (default: 0.1)
+    --frame-rate X          Frames per second (default: 60)
+    --vector-color COLOR    Colour of the vectors, as #RRGGBB or r,g,b
+    --path-color COLOR      Colour of the drawn path
+    --background COLOR      Background colour
+    --trail-length N        Number of points kept in the drawn path (default: 1000)
+    --paused                Start paused
+    --export FILE           Render one period to an animated GIF instead of opening a window
+    --png-frames DIR        Render numbered PNG frames into DIR instead of opening a window
+    --raw-frames            Write raw RGB24 frames to stdout instead of opening a window
+    --frames N              Frames to render (default: 100 for GIFs, one period otherwise)
+    --export-svg FILE       Write the reconstructed path to an SVG instead of opening a window
+    --epicycles-at T        Also draw the vectors at time T (0 to 1) in the exported SVG
+    --save-coefficients FILE
+                            Save the coefficients to a .json or .csv file instead of opening a window
+    -h, --help              Print this message";
+
+fn parse_number<T: std::str::FromStr>(flag: &str, value: &str) -> Result<T, String> {
+    value.parse().map_err(|_| format!("invalid value for {}: {}", flag, value))
+}
+
+fn parse_size(value: &str) -> Result<(u32, u32), String> {
+    let invalid = || format!("invalid value for --size: {}", value);
+    let (width, height) = value.split_once('x').ok_or_else(invalid)?;
+    Ok((width.parse().map_err(|_| invalid())?, height.parse().map_err(|_| invalid())?))
+}
+
+fn parse_color(flag: &str, value: &str) -> Result<Color, String> {
+    let invalid = || format!("invalid colour for {}: {}", flag, value);
+
+    if let Some(hex) = value.strip_prefix('#') {
+        // from_str_radix alone would also accept a sign
+        if hex.len() != 6 || !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+            return Err(invalid());
+        }
+        let channel = |i: usize| u8::from_str_radix(&hex[i..i + 2], 16).map_err(|_| invalid());
+        return Ok(Color::RGB(channel(0)?, channel(2)?, channel(4)?));
+    }
+
+    let channels = value.split(',')
+        .map(|channel| channel.trim().parse::<u8>())
+        .collect::<Result<Vec<u8>, _>>()
+        .map_err(|_| invalid())?;
+    match channels.as_slice() {
+        &[r, g, b] => Ok(Color::RGB(r, g, b)),
+        _ => Err(invalid())
+    }
+}
+
+enum Output {
+    Window,
+    Gif(PathBuf),
+    PngFrames(PathBuf),
+    RawFrames,
+    Svg(PathBuf),
+    Coefficients(PathBuf)
+}
+
+// Keeps the flag that chose the output, so a second one can be reported
+fn set_output(output: &mut Option<(String, Output)>, flag: &str, new: Output) -> Result<(), String> {
+    match output {
+        Some((previous, _)) => Err(format!("{} cannot be combined with {}", flag, previous)),
+        None => {
+            *output = Some((flag.to_string(), new));
+            Ok(())
+        }
+    }
+}
+
+struct Args {
+    config: FourierSeriesConfig,
+    output: Output,
+    frames: Option<usize>,
+    epicycles_at: Option<f64>
+}
+
+fn parse_args<I: Iterator<Item=String>>(mut args: I) -> Result<Option<Args>, String> {
+    let mut config = FourierSeriesConfig::default();
+    let mut input = None;
+    let mut output = None;
+    let mut frames = None;
+    let mut epicycles_at = None;
+
+    while let Some(arg) = args.next() {
+        let mut value = || args.next().ok_or_else(|| format!("missing value for {}", arg));
+
+        match arg.as_str() {
+            "-h" | "--help" => return Ok(None),
+            "--size" => {
+                let (width, height) = parse_size(&value()?)?;
+                config.width = width;
+                config.height = height;
+            },
+            "--title" => config.title = value()?,
+            "--max-frequency" => config.fourier.max_frequency = parse_number(&arg, &value()?)?,
+            "--terms" => match parse_number(&arg, &value()?)? {
+                0 => return Err(format!("invalid value for {}: 0", arg)),
+                count => config.fourier.terms = TermSelection::Count(count)
+            },
+            "--samples" => config.fourier.samples = parse_number(&arg, &value()?)?,
+            "--time-scale" => config.time_scale = parse_number(&arg, &value()?)?,
+            "--frame-rate" => config.frame_rate = parse_number(&arg, &value()?)?,
+            "--vector-color" => config.svector_color = parse_color(&arg, &value()?)?,
+            "--path-color" => config.point_path_color = parse_color(&arg, &value()?)?,
+            "--background" => config.background = parse_color(&arg, &value()?)?,
+            "--trail-length" => config.trail_length = parse_number(&arg, &value()?)?,
+            "--paused" => config.start_paused = true,
+            "--export" => set_output(&mut output, &arg, Output::Gif(PathBuf::from(value()?)))?,
+            "--png-frames" => set_output(&mut output, &arg, Output::PngFrames(PathBuf::from(value()?)))?,
+            "--raw-frames" => set_output(&mut output, &arg, Output::RawFrames)?,
+            "--frames" => frames = Some(parse_number(&arg, &value()?)?),
+            "--export-svg" => set_output(&mut output, &arg, Output::Svg(PathBuf::from(value()?)))?,
+            "--save-coefficients" => set_output(&mut output, &arg, Output::Coefficients(PathBuf::from(value()?)))?,
+            "--epicycles-at" => epicycles_at = Some(parse_number(&arg, &value()?)?),
+            _ if arg.starts_with('-') => return Err(format!("unknown option: {}", arg)),
+            _ if input.is_some() => return Err(format!("unexpected argument: {}", arg)),
+            _ => input = Some(arg)
+        }
+    }
+
+    if let Some(input) = input {
+        config.input = input.into();
+    }
+
+    let output = output.map_or(Output::Window, |(_, output)| output);
+    if frames.is_some() && !matches!(output, Output::Gif(_) | Output::PngFrames(_) | Output::RawFrames) {
+        return Err("--frames needs --export, --png-frames or --raw-frames".to_string());
+    }
+    if epicycles_at.is_some() && !matches!(output, Output::Svg(_)) {
+        return Err("--epicycles-at needs --export-svg".to_string());
+    }
+
+    Ok(Some(Args { config, output, frames, epicycles_at }))
+}
+
+fn export(args: Args) -> Result<(), String> {
+    let Args { config, output, frames, epicycles_at } = args;
+
+    if let Output::Coefficients(path) = output {
+        let file = load_coefficients(&config).map_err(|err| format!("Failed to start: {}", err))?;
+        return file.save(&path).map_err(|err| format!("Failed to save {}: {}", path.display(), err));
+    }
+    let period_frames = (config.frame_rate / config.time_scale).round() as usize;
+    let mut options = ExportOptions { frame_rate: config.frame_rate, ..ExportOptions::default() };
+
+    let renderer = RasterRenderer::new(config.width, config.height);
+    let mut fs = FourierSeriesBuilder::from(config).build_with_renderer(renderer)
+        .map_err(|err| format!("Failed to start: {}", err))?;
+
+    let result = match output {
+        Output::Window | Output::Coefficients(_) => Ok(()),
+        Output::Gif(path) => {
+            options.frames = frames.unwrap_or(options.frames);
+            let file = File::create(&path).map_err(|err| format!("Failed to create {}: {}", path.display(), err))?;
+            export_gif(&mut fs, BufWriter::new(file), options)
+        },
+        Output::PngFrames(directory) => {
+            options.frames = frames.unwrap_or(period_frames);
+            export_png_frames(&mut fs, directory, options)
+        },
+        Output::RawFrames => {
+            options.frames = frames.unwrap_or(period_frames);
+            export_raw_frames(&mut fs, BufWriter::new(io::stdout().lock()), options)
+        },
+        Output::Svg(path) => {
+            let options = SvgExportOptions {
+                epicycles_at,
+                path_color: fs.get_point_path_color(),
+                ..SvgExportOptions::default()
+            };
+            export_svg(fs.get_world(), path, options)
+        }
+    };
+
+    result.map_err(|err| format!("Failed to export: {}", err))
+}
+
 fn main() {
-    let mut fs = FourierSeries::new();
+    let args = match parse_args(env::args().skip(1)) {
+        Ok(Some(args)) => args,
+        Ok(None) => {
+            println!("{}", USAGE);
+            return;
+        },
+        Err(err) => {
+            eprintln!("{}\n\n{}", err, USAGE);
+            process::exit(2);
+        }
+    };
+
+    if !matches!(args.output, Output::Window) {
+        if let Err(err) = export(args) {
+            eprintln!("{}", err);
+            process::exit(1);
+        }
+        return;
+    }
+
+    show_window(args.config);
+}
+
+#[cfg(feature = "sdl")]
+fn show_window(config: FourierSeriesConfig) {
+    let mut fs = match FourierSeriesBuilder::from(config).build() {
+        Ok(fs) => fs,
+        Err(err) => {
+            eprintln!("Failed to start: {}", err);
+            process::exit(1);
+        }
+    };
 
     fs.mainloop();
 }
+
+#[cfg(not(feature = "sdl"))]
+fn show_window(_config: FourierSeriesConfig) {
+    eprintln!("Built without the sdl feature, so there is no window. Use one of the export options instead");
+    process::exit(1);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse(args: &[&str]) -> Result<Option<Args>, String> {
+        parse_args(args.iter().map(|arg| arg.to_string()))
+    }
+
+    fn parse_config(args: &[&str]) -> FourierSeriesConfig {
+        parse(args).unwrap().unwrap().config
+    }
+
+    #[test]
+    fn parses_size() {
+        let config = parse_config(&["--size", "1280x720"]);
+        assert_eq!((config.width, config.height), (1280, 720));
+
+        for size in ["1280", "1280x", "x720", "1280x-5", "wide"].iter() {
+            assert_eq!(parse(&["--size", size]).err(), Some(format!("invalid value for --size: {}", size)));
+        }
+    }
+
+    #[test]
+    fn parses_colors() {
+        assert_eq!(parse_color("--background", "#ff8000"), Ok(Color::RGB(255, 128, 0)));
+        assert_eq!(parse_color("--background", "#FF8000"), Ok(Color::RGB(255, 128, 0)));
+        assert_eq!(parse_color("--background", "255, 128,0"), Ok(Color::RGB(255, 128, 0)));
+        assert_eq!(parse_config(&["--path-color", "1,2,3"]).point_path_color, Color::RGB(1, 2, 3));
+
+        for color in ["#ff800", "#ff80000", "#gg8000", "#+f8000", "#éé00", "#ff80é", "256,0,0", "1,2", "1,2,3,4", "red"].iter() {
+            assert_eq!(parse_color("--background", color), Err(format!("invalid colour for --background: {}", color)));
+        }
+    }
+
+    #[test]
+    fn rejects_zero_terms() {
+        assert_eq!(parse_config(&["--terms", "5"]).fourier.terms, TermSelection::Count(5));
+        assert_eq!(parse(&["--terms", "0"]).err(), Some("invalid value for --terms: 0".to_string()));
+    }
+
+    #[test]
+    fn rejects_malformed_arguments() {
+        assert_eq!(parse(&["--samples"]).err(), Some("missing value for --samples".to_string()));
+        assert_eq!(parse(&["--samples", "many"]).err(), Some("invalid value for --samples: many".to_string()));
+        assert_eq!(parse(&["--fast"]).err(), Some("unknown option: --fast".to_string()));
+        assert_eq!(parse(&["a.svg", "b.svg"]).err(), Some("unexpected argument: b.svg".to_string()));
+        assert!(matches!(parse(&["--help"]), Ok(None)));
+    }
+
+    #[test]
+    fn parses_input_and_output() {
+        let args = parse(&["drawing.svg", "--export", "out.gif", "--frames", "20"]).unwrap().unwrap();
+
+        assert_eq!(args.config.input, PathBuf::from("drawing.svg"));
+        assert!(matches!(&args.output, Output::Gif(path) if path == &PathBuf::from("out.gif")));
+        assert_eq!(args.frames, Some(20));
+        assert!(matches!(parse(&[]).unwrap().unwrap().output, Output::Window));
+    }
+
+    #[test]
+    fn rejects_conflicting_outputs() {
+        assert_eq!(
+            parse(&["--export", "out.gif", "--export-svg", "out.svg"]).err(),
+            Some("--export-svg cannot be combined with --export".to_string())
+        );
+        assert_eq!(
+            parse(&["--raw-frames", "--raw-frames"]).err(),
+            Some("--raw-frames cannot be combined with --raw-frames".to_string())
+        );
+    }
+
+    #[test]
+    fn rejects_options_without_their_output() {
+        assert_eq!(
+            parse(&["--frames", "10"]).err(),
+            Some("--frames needs --export, --png-frames or --raw-frames".to_string())
+        );
+        assert!(parse(&["--frames", "10", "--export-svg", "out.svg"]).is_err());
+        assert_eq!(
+            parse(&["--epicycles-at", "0.5", "--export", "out.gif"]).err(),
+            Some("--epicycles-at needs --export-svg".to_string())
+        );
+        assert!(parse(&["--export-svg", "out.svg", "--epicycles-at", "0.5"]).is_ok());
+    }
+}
@@ -1,13 +1,126 @@
+use std::f64::consts::PI;
+
 use super::common::Point;
 
 pub fn point_average<T>(points: T) -> Point where T: Iterator<Item=Point> {
-    let mut total = Point::ZERO;
     let mut count = 0;
+    let total: Point = points.inspect(|_| count += 1).sum();
+
+    total / count as f64
+}
+
+// Prime factors above this are transformed with Bluestein's algorithm instead of a direct DFT
+const BLUESTEIN_MIN_RADIX: usize = 32;
+
+// Discrete Fourier transform using e^(-2*pi*i*k*n/N), with a mixed-radix
+// Cooley-Tukey split on the smallest prime factor of the input length
+pub fn fft(input: &[Point]) -> Vec<Point> {
+    let n = input.len();
+    if n <= 1 {
+        return input.to_vec();
+    }
+
+    let radix = (2..).find(|&p| n.is_multiple_of(p)).unwrap();
+    let m = n / radix;
+
+    if radix >= BLUESTEIN_MIN_RADIX {
+        return bluestein(input);
+    }
+    if m == 1 {
+        return dft(input);
+    }
+
+    let sub_spectra: Vec<Vec<Point>> = (0..radix)
+        .map(|r| {
+            let subsequence: Vec<Point> = input.iter().skip(r).step_by(radix).cloned().collect();
+            fft(&subsequence)
+        })
+        .collect();
+
+    (0..n).map(|k| {
+        sub_spectra.iter().enumerate()
+            .map(|(r, sub_spectrum)| sub_spectrum[k % m] * Point::from_ei(-2.0 * PI * (r * k) as f64 / n as f64))
+            .sum()
+    }).collect()
+}
+
+// Rewrites the transform as a convolution with a chirp, which is done with
+// power of two FFTs, so any length stays O(N log N)
+fn bluestein(input: &[Point]) -> Vec<Point> {
+    let n = input.len();
+    let size = (2 * n - 1).next_power_of_two();
+
+    // e^(-pi*i*j^2/N), reducing j^2 first to keep the angle accurate
+    let chirp: Vec<Point> = (0..n)
+        .map(|j| Point::from_ei(-PI * ((j * j) % (2 * n)) as f64 / n as f64))
+        .collect();
+
+    let mut a = vec![Point::ZERO; size];
+    let mut b = vec![Point::ZERO; size];
+    for j in 0..n {
+        a[j] = input[j] * chirp[j];
+        b[j] = chirp[j].conj();
+        if j > 0 {
+            b[size - j] = chirp[j].conj();
+        }
+    }
+
+    let (a, b) = (fft(&a), fft(&b));
+    let product: Vec<Point> = a.iter().zip(b.iter()).map(|(&x, &y)| (x * y).conj()).collect();
+    // The inverse transform, via conj(fft(conj(x))) / size
+    let convolution = fft(&product);
+
+    (0..n).map(|k| chirp[k] * convolution[k].conj() / size as f64).collect()
+}
+
+fn dft(input: &[Point]) -> Vec<Point> {
+    let n = input.len();
+
+    (0..n).map(|k| {
+        input.iter().enumerate()
+            .map(|(j, &point)| point * Point::from_ei(-2.0 * PI * ((j * k) % n) as f64 / n as f64))
+            .sum()
+    }).collect()
+}
+
+// The integral of (c0 + c1*t + c2*t^2 + ...) * e^(-2*pi*i*frequency*t) over 0 <= t <= 1
+pub fn polynomial_fourier_integral(coefficients: &[Point], frequency: f64) -> Point {
+    let a = Point::I * (-2.0 * PI * frequency);
+
+    // Near zero frequency the closed form cancels badly, so expand e^(at) instead
+    if a.mag() < 1.0 {
+        let mut total = Point::ZERO;
+        for (m, &c) in coefficients.iter().enumerate() {
+            let mut term = c;
+            for j in 0..30 {
+                if j > 0 {
+                    term *= a / j as f64;
+                }
+                total += term / (m + j + 1) as f64;
+            }
+        }
+        return total;
+    }
+
+    // Integrating by parts: e^(at) * sum_j (-1)^j p^(j)(t) / a^(j+1)
+    let mut derivatives = coefficients.to_vec();
+    let mut inv_a_power = a.recip();
+    let mut at_one = Point::ZERO;
+    let mut at_zero = Point::ZERO;
+
+    for j in 0..coefficients.len() {
+        let sign = if j % 2 == 0 { 1.0 } else { -1.0 };
+        let value_at_one: Point = derivatives.iter().sum();
+        let value_at_zero = derivatives.first().cloned().unwrap_or(Point::ZERO);
+
+        at_one += value_at_one * inv_a_power * sign;
+        at_zero += value_at_zero * inv_a_power * sign;
 
-    for point in points {
-        total += point;
-        count += 1;
+        derivatives = derivatives.iter().enumerate().skip(1)
+            .map(|(m, &c)| c * m as f64)
+            .collect();
+        inv_a_power *= a.recip();
     }
 
-    total.scale(1.0/count as f64)
-}
\ No newline at end of file
+    a.exp() * at_one - at_zero
+}
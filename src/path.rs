@@ -1,5 +1,21 @@
+use std::f64::consts::PI;
+
 use super::common::Point;
+use super::maths::point_average;
 
 pub trait ParametricPath {
     fn get_point(&self, t: f64) -> Point;
-}
\ No newline at end of file
+
+    // The integral of get_point(t) * e^(-2*pi*i*frequency*t) over 0 <= t <= 1
+    fn fourier_coefficient(&self, frequency: f64, samples: usize) -> Point {
+        sampled_fourier_coefficient(self, frequency, samples)
+    }
+}
+
+pub fn sampled_fourier_coefficient<P: ParametricPath + ?Sized>(path: &P, frequency: f64, samples: usize) -> Point {
+    let points = (0..samples)
+        .map(|x| x as f64 / samples as f64)
+        .map(|x| path.get_point(x) * (Point::I * (-2.0 * PI * frequency * x)).exp());
+
+    point_average(points)
+}
@@ -0,0 +1,138 @@
+use super::color::Color;
+use super::common::Point;
+use super::renderer::{Camera, Renderer};
+
+// Software renderer drawing into an in-memory RGBA buffer, so frames can be
+// produced without a window or video subsystem
+pub struct RasterRenderer {
+    width: u32,
+    height: u32,
+    pixels: Vec<u8>,
+    color: Color,
+    camera: Camera
+}
+
+impl RasterRenderer {
+    pub fn new(width: u32, height: u32) -> Self {
+        Self {
+            width, height,
+            pixels: vec![0; width as usize * height as usize * 4],
+            color: Color::RGB(255, 255, 255),
+            camera: Camera::default()
+        }
+    }
+
+    pub fn get_width(&self) -> u32 {
+        self.width
+    }
+
+    pub fn get_height(&self) -> u32 {
+        self.height
+    }
+
+    // Rows top to bottom, four bytes (r, g, b, a) per pixel
+    pub fn get_pixels(&self) -> &[u8] {
+        &self.pixels
+    }
+
+    pub fn get_pixel(&self, x: u32, y: u32) -> Option<Color> {
+        if x >= self.width || y >= self.height {
+            return None;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        Some(Color::RGBA(self.pixels[i], self.pixels[i + 1], self.pixels[i + 2], self.pixels[i + 3]))
+    }
+
+    fn set_pixel(&mut self, x: i64, y: i64) {
+        if x < 0 || y < 0 || x >= self.width as i64 || y >= self.height as i64 {
+            return;
+        }
+        let i = (y as usize * self.width as usize + x as usize) * 4;
+        self.pixels[i..i + 4].copy_from_slice(&[self.color.r, self.color.g, self.color.b, self.color.a]);
+    }
+
+    // Liang-Barsky clipping against the pixel grid, so lines far off screen
+    // after zooming in don't cost anything to draw
+    fn clip(&self, start: Point, end: Point) -> Option<(Point, Point)> {
+        let delta = end - start;
+        let (mut t0, mut t1) = (0.0_f64, 1.0_f64);
+        let max_x = self.width as f64 - 0.5;
+        let max_y = self.height as f64 - 0.5;
+
+        let edges = [
+            (-delta.x, start.x + 0.5),
+            (delta.x, max_x - start.x),
+            (-delta.y, start.y + 0.5),
+            (delta.y, max_y - start.y)
+        ];
+
+        for &(p, q) in edges.iter() {
+            if p == 0.0 {
+                if q < 0.0 {
+                    return None;
+                }
+            } else {
+                let r = q / p;
+                if p < 0.0 {
+                    t0 = t0.max(r);
+                } else {
+                    t1 = t1.min(r);
+                }
+            }
+        }
+
+        if t0 > t1 {
+            None
+        } else {
+            Some((start + delta * t0, start + delta * t1))
+        }
+    }
+}
+
+impl Renderer for RasterRenderer {
+    fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String> {
+        let start = self.point_to_pixel(start);
+        let end = self.point_to_pixel(end);
+
+        if !(start.x.is_finite() && start.y.is_finite() && end.x.is_finite() && end.y.is_finite()) {
+            return Err(String::from("Cannot draw a line to a non-finite point"));
+        }
+
+        if let Some((start, end)) = self.clip(start, end) {
+            let delta = end - start;
+            let steps = delta.x.abs().max(delta.y.abs()).ceil().max(1.0) as usize;
+
+            for i in 0..=steps {
+                let point = start + delta * (i as f64 / steps as f64);
+                self.set_pixel(point.x.round() as i64, point.y.round() as i64);
+            }
+        }
+
+        Ok(())
+    }
+
+    fn clear(&mut self) {
+        let color = [self.color.r, self.color.g, self.color.b, self.color.a];
+        for pixel in self.pixels.chunks_exact_mut(4) {
+            pixel.copy_from_slice(&color);
+        }
+    }
+
+    fn set_color(&mut self, color: Color) {
+        self.color = color;
+    }
+
+    fn present(&mut self) {}
+
+    fn get_size(&self) -> (u32, u32) {
+        (self.width, self.height)
+    }
+
+    fn get_camera(&self) -> &Camera {
+        &self.camera
+    }
+
+    fn get_camera_mut(&mut self) -> &mut Camera {
+        &mut self.camera
+    }
+}
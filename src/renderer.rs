@@ -0,0 +1,105 @@
+use super::color::Color;
+use super::common::Point;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Camera {
+    center: Point,
+    scale: f64
+}
+
+impl Default for Camera {
+    fn default() -> Self {
+        Self::new(Point::ZERO, 0.03)
+    }
+}
+
+impl Camera {
+    pub fn new(center: Point, scale: f64) -> Self {
+        Self { center, scale }
+    }
+
+    pub fn get_center(&self) -> Point {
+        self.center
+    }
+
+    pub fn set_center(&mut self, center: Point) {
+        self.center = center;
+    }
+
+    pub fn get_scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn set_scale(&mut self, scale: f64) {
+        self.scale = scale;
+    }
+
+    // Centres the points on a screen of the given size, leaving `margin` pixels free at the edges
+    pub fn fit(points: &[Point], (width, height): (u32, u32), margin: f64) -> Self {
+        let (min, max) = points.iter().fold(
+            (Point::new(f64::INFINITY, f64::INFINITY), Point::new(f64::NEG_INFINITY, f64::NEG_INFINITY)),
+            |(min, max), p| (Point::new(min.x.min(p.x), min.y.min(p.y)), Point::new(max.x.max(p.x), max.y.max(p.y)))
+        );
+        if points.is_empty() {
+            return Self::default();
+        }
+
+        let size = max - min;
+        let scale_x = (width as f64 - 2.0 * margin).max(1.0) / size.x;
+        let scale_y = (height as f64 - 2.0 * margin).max(1.0) / size.y;
+        let scale = scale_x.min(scale_y);
+
+        Self::new((min + max) / 2.0, if scale.is_finite() { scale } else { 1.0 })
+    }
+
+    // World coordinates (y up) to pixel coordinates (y down) on a screen of the given size
+    pub fn point_to_pixel(&self, point: Point, (width, height): (u32, u32)) -> Point {
+        let point = (point - self.center).scale(self.scale);
+        Point::new(point.x + width as f64 / 2.0, -point.y + height as f64 / 2.0)
+    }
+
+    pub fn pixel_to_point(&self, x: i32, y: i32, (width, height): (u32, u32)) -> Point {
+        let point = Point::new(x as f64 - width as f64 / 2.0, -y as f64 + height as f64 / 2.0);
+        point.scale(self.scale.recip()) + self.center
+    }
+}
+
+pub trait Renderer {
+    fn draw_line(&mut self, start: Point, end: Point) -> Result<(), String>;
+
+    fn clear(&mut self);
+
+    fn set_color(&mut self, color: Color);
+
+    fn present(&mut self);
+
+    fn get_size(&self) -> (u32, u32);
+
+    fn get_camera(&self) -> &Camera;
+
+    fn get_camera_mut(&mut self) -> &mut Camera;
+
+    fn set_camera_pos(&mut self, p: Point) {
+        self.get_camera_mut().set_center(p);
+    }
+
+    fn move_camera_by_pixels(&mut self, x: i32, y: i32) {
+        let camera = self.get_camera_mut();
+        let offset = Point::new(-x as f64, y as f64).scale(camera.get_scale().recip());
+        camera.set_center(camera.get_center() + offset);
+    }
+
+    // Zooms keeping the point under the pixel (center_x, center_y) fixed
+    fn zoom(&mut self, scale: f64, center_x: i32, center_y: i32) {
+        let size = self.get_size();
+        let camera = self.get_camera_mut();
+        let point_before = camera.pixel_to_point(center_x, center_y, size);
+        camera.set_scale(camera.get_scale() * scale);
+        let point_after = camera.pixel_to_point(center_x, center_y, size);
+        camera.set_center(camera.get_center() - (point_after - point_before));
+    }
+
+    fn point_to_pixel(&self, point: Point) -> Point {
+        self.get_camera().point_to_pixel(point, self.get_size())
+    }
+}
@@ -1,88 +1,656 @@
 extern crate svg;
 
+use std::error::Error;
+use std::f64::consts::PI;
+use std::fmt;
+use std::io::{self, Read};
 use std::ops::Deref;
 use std::path::Path;
+use std::str::FromStr;
 
-use svg::parser::{Event, Parser};
+use svg::parser::{Error as ParseError, Event, Parser};
 use svg::node::element::path::{Command, Data, Number, Parameters, Position};
-use svg::node::element::tag::Path as PathTag;
-use svg::node::Value;
+use svg::node::element::tag::{self, Type};
+use svg::node::Attributes;
+use serde::{Deserialize, Serialize};
 
 use super::common::Point;
-use super::path::ParametricPath;
+use super::maths::polynomial_fourier_integral;
+use super::path::{sampled_fourier_coefficient, ParametricPath};
+use super::transform::Transform;
+
+const ARC_LENGTH_SAMPLES: usize = 100;
+const MIN_SEGMENT_SAMPLES: usize = 1000;
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Parameterization {
+    SegmentIndex,
+    ArcLength
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum Origin {
+    Unchanged,
+    Centroid,
+    ViewBoxCenter
+}
+
+#[derive(Copy, Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct Normalization {
+    pub flip_y: bool,
+    pub origin: Origin,
+    // Uniformly scales the drawing so its larger side has this length
+    pub fit_to: Option<f64>
+}
+
+impl Default for Normalization {
+    fn default() -> Self {
+        Self { flip_y: true, origin: Origin::Centroid, fit_to: None }
+    }
+}
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct ViewBox {
+    pub min: Point,
+    pub size: Point
+}
+
+#[derive(Debug)]
+pub enum SvgLoadError {
+    Io(io::Error),
+    NoPathFound,
+    ParseError(ParseError),
+    InvalidTransform(String),
+    // The attribute and its value, for lengths in units that need layout, e.g. %
+    InvalidLength(String, String),
+    WrongParameterCount(char, usize)
+}
+
+impl From<io::Error> for SvgLoadError {
+    fn from(value: io::Error) -> Self {
+        SvgLoadError::Io(value)
+    }
+}
+
+impl From<ParseError> for SvgLoadError {
+    fn from(value: ParseError) -> Self {
+        SvgLoadError::ParseError(value)
+    }
+}
+
+impl fmt::Display for SvgLoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SvgLoadError::Io(err) => write!(f, "could not read the SVG: {}", err),
+            SvgLoadError::NoPathFound => write!(f, "the SVG has nothing to draw"),
+            SvgLoadError::ParseError(err) => write!(f, "could not parse the SVG: {}", err),
+            SvgLoadError::InvalidTransform(value) => write!(f, "invalid transform attribute: {}", value),
+            SvgLoadError::InvalidLength(name, value) => write!(f, "unsupported length for {}: {}", name, value),
+            SvgLoadError::WrongParameterCount(command, count) => {
+                write!(f, "wrong number of parameters for path command {}: {}", command, count)
+            }
+        }
+    }
+}
+
+impl Error for SvgLoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            SvgLoadError::Io(err) => Some(err),
+            SvgLoadError::ParseError(err) => Some(err),
+            _ => None
+        }
+    }
+}
 
 pub struct SVGPath {
-    segments: Vec<Box<dyn ParametricPath>>
+    segments: Vec<Box<dyn Segment>>,
+    arc_lengths: Vec<(f64, f64)>,
+    parameterization: Parameterization,
+    view_box: Option<ViewBox>
 }
 
 impl SVGPath {
-    pub fn new<T: AsRef<Path>>(path: T) -> Result<SVGPath, ()> {
-        let svg_file = svg::open(path).unwrap();
-
-        let data = get_first_path_data(svg_file).unwrap();
-        let data = Data::parse(&data).unwrap();
-
-        let mut segments: Vec<Box<dyn ParametricPath>> = Vec::new();
-
-        let mut cur_pos = Point::ZERO;
-
-        for command in data.iter() {
-            match &command {
-                &Command::Move(pos, params) => {
-                    let numbers: &[Number] = params.deref();
-                    let new_pos = Point::new(
-                        *(numbers.get(0).unwrap()) as f64,
-                        *(numbers.get(1).unwrap()) as f64
-                    );
-                    match pos {
-                        Position::Absolute => cur_pos = new_pos,
-                        Position::Relative => cur_pos += new_pos
-                    };
+    pub fn new<T: AsRef<Path>>(path: T) -> Result<SVGPath, SvgLoadError> {
+        SVGPath::from_parser(svg::open(path)?)
+    }
+
+    pub fn from_reader<R: Read>(reader: R) -> Result<SVGPath, SvgLoadError> {
+        SVGPath::from_parser(svg::read(reader)?)
+    }
+
+    pub fn from_path_data(data: &str) -> Result<SVGPath, SvgLoadError> {
+        let segments = build_segments(&Data::parse(data)?)?;
+
+        if segments.is_empty() {
+            Err(SvgLoadError::NoPathFound)
+        } else {
+            Ok(SVGPath::from_segments(segments))
+        }
+    }
+
+    fn from_parser(svg_file: Parser) -> Result<SVGPath, SvgLoadError> {
+        let (paths, view_box) = get_path_segments(svg_file)?;
+
+        let mut path = SVGPath::from_segments(join_segments(paths));
+        path.view_box = view_box;
+
+        Ok(path)
+    }
+
+    pub fn new_separate<T: AsRef<Path>>(path: T) -> Result<Vec<SVGPath>, SvgLoadError> {
+        let svg_file = svg::open(path)?;
+
+        let (paths, view_box) = get_path_segments(svg_file)?;
+
+        Ok(paths.into_iter()
+            .filter(|segments| !segments.is_empty())
+            .map(|segments| {
+                let mut path = SVGPath::from_segments(segments);
+                path.view_box = view_box;
+                path
+            })
+            .collect())
+    }
+
+    fn from_segments(segments: Vec<Box<dyn Segment>>) -> SVGPath {
+        let mut path = SVGPath {
+            segments,
+            arc_lengths: Vec::new(),
+            parameterization: Parameterization::SegmentIndex,
+            view_box: None
+        };
+        path.arc_lengths = path.build_arc_length_table();
+
+        path
+    }
+
+    pub fn set_parameterization(&mut self, parameterization: Parameterization) {
+        self.parameterization = parameterization;
+    }
+
+    pub fn get_parameterization(&self) -> Parameterization {
+        self.parameterization
+    }
+
+    pub fn get_length(&self) -> f64 {
+        self.arc_lengths.last().map_or(0.0, |&(length, _)| length)
+    }
+
+    pub fn get_view_box(&self) -> Option<ViewBox> {
+        self.view_box
+    }
+
+    pub fn get_centroid(&self) -> Point {
+        let mut total = Point::ZERO;
+        let mut last = match self.arc_lengths.first() {
+            Some(&(_, t)) => (0.0, self.get_index_point(t)),
+            None => return Point::ZERO
+        };
+
+        for &(length, t) in self.arc_lengths.iter().skip(1) {
+            let point = self.get_index_point(t);
+            total += (point + last.1).scale(0.5 * (length - last.0));
+            last = (length, point);
+        }
+
+        if self.get_length() > 0.0 {
+            total.scale(self.get_length().recip())
+        } else {
+            last.1
+        }
+    }
+
+    pub fn get_bounds(&self) -> (Point, Point) {
+        let mut points = self.arc_lengths.iter().map(|&(_, t)| self.get_index_point(t));
+
+        let first = points.next().unwrap_or(Point::ZERO);
+        points.fold((first, first), |(min, max), point| (
+            Point::new(min.x.min(point.x), min.y.min(point.y)),
+            Point::new(max.x.max(point.x), max.y.max(point.y))
+        ))
+    }
+
+    pub fn normalize(&mut self, normalization: Normalization) {
+        let origin = match (normalization.origin, self.view_box) {
+            (Origin::Unchanged, _) => Point::ZERO,
+            (Origin::ViewBoxCenter, Some(view_box)) => view_box.min + view_box.size.scale(0.5),
+            _ => self.get_centroid()
+        };
+
+        let mut transform = Transform::translate(-origin.x, -origin.y);
+        if normalization.flip_y {
+            transform = Transform::scale(1.0, -1.0).multiply(&transform);
+        }
+
+        if let Some(fit_to) = normalization.fit_to {
+            let (min, max) = self.get_bounds();
+            let extent = (max.x - min.x).max(max.y - min.y);
+            if extent > 0.0 {
+                transform = Transform::scale(fit_to / extent, fit_to / extent).multiply(&transform);
+            }
+        }
+
+        for segment in self.segments.iter_mut() {
+            segment.transform(&transform);
+        }
+
+        if let Some(view_box) = self.view_box {
+            let corner1 = transform.apply(view_box.min);
+            let corner2 = transform.apply(view_box.min + view_box.size);
+            let min = Point::new(corner1.x.min(corner2.x), corner1.y.min(corner2.y));
+            let max = Point::new(corner1.x.max(corner2.x), corner1.y.max(corner2.y));
+            self.view_box = Some(ViewBox { min, size: max - min });
+        }
+
+        self.arc_lengths = self.build_arc_length_table();
+    }
+
+    fn build_arc_length_table(&self) -> Vec<(f64, f64)> {
+        if self.segments.is_empty() {
+            return Vec::new();
+        }
+
+        let samples = self.segments.len() * ARC_LENGTH_SAMPLES;
+        let mut table = Vec::with_capacity(samples + 1);
+
+        let mut length = 0.0;
+        let mut last_point = self.get_index_point(0.0);
+        table.push((length, 0.0));
+
+        for i in 1..=samples {
+            let t = i as f64 / samples as f64;
+            let point = self.get_index_point(t);
+            length += (point - last_point).mag();
+            last_point = point;
+            table.push((length, t));
+        }
+
+        table
+    }
+
+    fn get_index_point(&self, t: f64) -> Point {
+        let scaled_t = t * self.segments.len() as f64;
+        let segment_index = (scaled_t.floor() as usize).min(self.segments.len() - 1);
+        let sub_t = scaled_t - segment_index as f64;
+
+        self.segments[segment_index].get_point(sub_t)
+    }
+
+    fn arc_length_to_index_t(&self, t: f64) -> f64 {
+        let target = t * self.get_length();
+        let upper = self.arc_lengths.partition_point(|&(length, _)| length < target);
+
+        if upper == 0 {
+            return 0.0;
+        } else if upper == self.arc_lengths.len() {
+            return 1.0;
+        }
+
+        let (length_before, t_before) = self.arc_lengths[upper - 1];
+        let (length_after, t_after) = self.arc_lengths[upper];
+        let fraction = (target - length_before) / (length_after - length_before);
+        t_before + (t_after - t_before) * fraction
+    }
+}
+
+// Shapes inside these are only drawn when referenced from elsewhere
+const NON_RENDERED_CONTAINERS: [&str; 5] = [
+    tag::ClipPath, tag::Definitions, tag::Marker, tag::Mask, tag::Symbol
+];
+
+type PathSegments = (Vec<Vec<Box<dyn Segment>>>, Option<ViewBox>);
+
+fn get_path_segments(svg_file: Parser) -> Result<PathSegments, SvgLoadError> {
+    let mut paths: Vec<Vec<Box<dyn Segment>>> = Vec::new();
+    // Each open element's transform, and whether it sits inside a container that is never rendered
+    let mut stack = vec![(Transform::IDENTITY, false)];
+    let mut view_box: Option<ViewBox> = None;
+
+    for event in svg_file {
+        if let Event::Error(err) = event {
+            return Err(SvgLoadError::from(err));
+        } else if let Event::Tag(name, tag_type, attributes) = event {
+            if tag_type == Type::End {
+                if stack.len() > 1 {
+                    stack.pop();
+                }
+                continue;
+            }
+
+            let (parent, parent_hidden) = *stack.last().unwrap();
+            let hidden = parent_hidden || NON_RENDERED_CONTAINERS.contains(&name);
+            let transform = match attributes.get("transform") {
+                Some(value) => parent.multiply(
+                    &Transform::parse(value).ok_or_else(|| SvgLoadError::InvalidTransform(value.to_string()))?
+                ),
+                None => parent
+            };
+            if tag_type == Type::Start {
+                stack.push((transform, hidden));
+            }
+
+            if name == tag::SVG && view_box.is_none() {
+                view_box = get_view_box(&attributes);
+            }
+            if hidden {
+                continue;
+            }
+
+            let mut segments = match name {
+                tag::Path => match attributes.get("d") {
+                    Some(value) => build_segments(&Data::parse(value)?)?,
+                    None => continue
+                },
+                tag::Rectangle => rect_segments(&attributes)?,
+                tag::Circle => {
+                    let radius = get_number(&attributes, "r")?;
+                    ellipse_segments(&attributes, Point::new(radius, radius))?
                 },
-                &Command::Close => {},
-                &Command::Line(pos, params) => segments.append(&mut Line::from_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::HorizontalLine(pos, params) => segments.append(&mut Line::from_horiz_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::VerticalLine(pos, params) => segments.append(&mut Line::from_vert_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::QuadraticCurve(pos, params) => segments.append(&mut QuadraticBezierCurve::from_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::SmoothQuadraticCurve(pos, params) => segments.append(&mut QuadraticBezierCurve::from_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::CubicCurve(pos, params) => segments.append(&mut CubicBezierCurve::from_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::SmoothCubicCurve(pos, params) => segments.append(&mut CubicBezierCurve::from_parameters(cur_pos, pos, params, &mut cur_pos)),
-                &Command::EllipticalArc(pos, params) => segments.append(&mut EllipticalArc::from_parameters(cur_pos, pos, params, &mut cur_pos))
+                tag::Ellipse => {
+                    let radii = Point::new(get_number(&attributes, "rx")?, get_number(&attributes, "ry")?);
+                    ellipse_segments(&attributes, radii)?
+                },
+                tag::Line => vec![Box::new(Line::new(
+                    Point::new(get_number(&attributes, "x1")?, get_number(&attributes, "y1")?),
+                    Point::new(get_number(&attributes, "x2")?, get_number(&attributes, "y2")?)
+                )) as Box<dyn Segment>],
+                tag::Polyline => poly_segments(&attributes, false),
+                tag::Polygon => poly_segments(&attributes, true),
+                _ => continue
+            };
+            for segment in segments.iter_mut() {
+                segment.transform(&transform);
+            }
+            // Elements such as a lone moveto or a one point polyline draw nothing
+            if !segments.is_empty() {
+                paths.push(segments);
             }
         }
+    }
+
+    if paths.is_empty() {
+        Err(SvgLoadError::NoPathFound)
+    } else {
+        Ok((paths, view_box))
+    }
+}
+
+fn get_view_box(attributes: &Attributes) -> Option<ViewBox> {
+    let view_box = match attributes.get("viewBox") {
+        Some(value) => {
+            let numbers: Vec<f64> = value
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter_map(|number| number.parse().ok())
+                .collect();
+            match numbers.as_slice() {
+                &[x, y, width, height] => ViewBox { min: Point::new(x, y), size: Point::new(width, height) },
+                _ => return None
+            }
+        },
+        None => ViewBox {
+            min: Point::ZERO,
+            // A relative size such as 100% leaves the view box unknown
+            size: Point::new(
+                get_number(attributes, "width").unwrap_or(0.0),
+                get_number(attributes, "height").unwrap_or(0.0)
+            )
+        }
+    };
+
+    if view_box.size.x > 0.0 && view_box.size.y > 0.0 {
+        Some(view_box)
+    } else {
+        None
+    }
+}
 
-        Ok( SVGPath { segments } )
+// Absolute units in user units (px) per unit, the unitless case last
+const LENGTH_UNITS: [(&str, f64); 7] = [
+    ("px", 1.0), ("in", 96.0), ("cm", 96.0 / 2.54), ("mm", 96.0 / 25.4), ("pt", 96.0 / 72.0), ("pc", 16.0), ("", 1.0)
+];
+
+// A missing attribute is 0, as in SVG
+fn get_number(attributes: &Attributes, name: &str) -> Result<f64, SvgLoadError> {
+    let value = match attributes.get(name) {
+        Some(value) => value.trim(),
+        None => return Ok(0.0)
+    };
+
+    LENGTH_UNITS.iter()
+        .find_map(|&(unit, scale)| {
+            let number: f64 = value.strip_suffix(unit)?.trim_end().parse().ok()?;
+            Some(number * scale)
+        })
+        .ok_or_else(|| SvgLoadError::InvalidLength(name.to_string(), value.to_string()))
+}
+
+fn rect_segments(attributes: &Attributes) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+    let (x, y) = (get_number(attributes, "x")?, get_number(attributes, "y")?);
+    let (width, height) = (get_number(attributes, "width")?, get_number(attributes, "height")?);
+
+    // Rects without an area are not drawn
+    if width <= 0.0 || height <= 0.0 {
+        return Ok(Vec::new());
     }
+
+    let (mut rx, mut ry) = (get_number(attributes, "rx")?.abs(), get_number(attributes, "ry")?.abs());
+    if !attributes.contains_key("rx") {
+        rx = ry;
+    } else if !attributes.contains_key("ry") {
+        ry = rx;
+    }
+    // A zero radius on either axis gives square corners
+    if rx == 0.0 || ry == 0.0 {
+        rx = 0.0;
+        ry = 0.0;
+    }
+    let (rx, ry) = (rx.min(width / 2.0), ry.min(height / 2.0));
+
+    let outline = [
+        Point::new(x + rx, y), Point::new(x + width - rx, y),
+        Point::new(x + width, y + ry), Point::new(x + width, y + height - ry),
+        Point::new(x + width - rx, y + height), Point::new(x + rx, y + height),
+        Point::new(x, y + height - ry), Point::new(x, y + ry)
+    ];
+
+    let mut segments: Vec<Box<dyn Segment>> = Vec::new();
+
+    for i in (0..8).step_by(2) {
+        let (line_start, arc_start, arc_end) = (outline[i], outline[i + 1], outline[(i + 2) % 8]);
+
+        segments.push(Box::new(Line::new(line_start, arc_start)));
+        if rx > 0.0 && ry > 0.0 {
+            segments.push(Box::new(EllipticalArc::new(arc_start, Point::new(rx, ry), 0.0, false, true, arc_end)));
+        }
+    }
+
+    Ok(segments)
 }
 
-fn get_first_path_data(mut svg_file: Parser) -> Result<String, ()> {
-    let mut path_data: Option<String> = None;
+fn ellipse_segments(attributes: &Attributes, radii: Point) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+    // As with rects, a zero radius means the shape is not drawn
+    if radii.x <= 0.0 || radii.y <= 0.0 {
+        return Ok(Vec::new());
+    }
+
+    let center = Point::new(get_number(attributes, "cx")?, get_number(attributes, "cy")?);
+    let quadrants = [
+        Point::new(radii.x, 0.0),
+        Point::new(0.0, radii.y),
+        Point::new(-radii.x, 0.0),
+        Point::new(0.0, -radii.y)
+    ];
 
-    while let Some(event) = svg_file.next() {
-        if let Event::Tag(PathTag, _, attributes) = event {
-            if let Some(value) = attributes.get("d") {
-                path_data = Some(value.to_string());
+    Ok((0..4).map(|i| Box::new(EllipticalArc::new(
+        center + quadrants[i],
+        radii, 0.0, false, true,
+        center + quadrants[(i + 1) % 4]
+    )) as Box<dyn Segment>).collect())
+}
+
+fn poly_segments(attributes: &Attributes, closed: bool) -> Vec<Box<dyn Segment>> {
+    let numbers: Vec<f64> = attributes.get("points")
+        .map(|value| value
+            .split(|c: char| c.is_whitespace() || c == ',')
+            .filter_map(|number| number.parse().ok())
+            .collect())
+        .unwrap_or_default();
+
+    let mut points: Vec<Point> = numbers.chunks_exact(2)
+        .map(|pair| Point::new(pair[0], pair[1]))
+        .collect();
+
+    if closed {
+        if let Some(&first) = points.first() {
+            points.push(first);
+        }
+    }
+
+    points.windows(2)
+        .map(|pair| Box::new(Line::new(pair[0], pair[1])) as Box<dyn Segment>)
+        .collect()
+}
+
+fn build_segments(data: &Data) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+    let mut segments: Vec<Box<dyn Segment>> = Vec::new();
+
+    let mut cur_pos = Point::ZERO;
+    let mut subpath_start = Point::ZERO;
+    let mut last_quadratic_control: Option<Point> = None;
+    let mut last_cubic_control: Option<Point> = None;
+
+    for command in data.iter() {
+        let mut quadratic_control: Option<Point> = None;
+        let mut cubic_control: Option<Point> = None;
+
+        match command {
+            Command::Move(pos, params) => {
+                check_parameters('M', pos, params, 2)?;
+
+                let numbers: &[Number] = params.deref();
+                let new_pos = Point::new(numbers[0] as f64, numbers[1] as f64);
+                match pos {
+                    Position::Absolute => cur_pos = new_pos,
+                    Position::Relative => cur_pos += new_pos
+                };
+                subpath_start = cur_pos;
+
+                if numbers.len() > 2 {
+                    let line_params = Parameters::from(numbers[2..].to_vec());
+                    segments.append(&mut Line::from_parameters(cur_pos, pos, &line_params, &mut cur_pos)?);
+                }
+            },
+            Command::Close => {
+                if cur_pos != subpath_start {
+                    segments.push(Box::new(Line::new(cur_pos, subpath_start)));
+                }
+                cur_pos = subpath_start;
+            },
+            Command::Line(pos, params) => segments.append(&mut Line::from_parameters(cur_pos, pos, params, &mut cur_pos)?),
+            Command::HorizontalLine(pos, params) => segments.append(&mut Line::from_horiz_parameters(cur_pos, pos, params, &mut cur_pos)?),
+            Command::VerticalLine(pos, params) => segments.append(&mut Line::from_vert_parameters(cur_pos, pos, params, &mut cur_pos)?),
+            Command::QuadraticCurve(pos, params) => segments.append(&mut QuadraticBezierCurve::from_parameters(cur_pos, pos, params, &mut cur_pos, &mut quadratic_control)?),
+            Command::SmoothQuadraticCurve(pos, params) => segments.append(&mut QuadraticBezierCurve::from_smooth_parameters(cur_pos, pos, params, &mut cur_pos, last_quadratic_control, &mut quadratic_control)?),
+            Command::CubicCurve(pos, params) => segments.append(&mut CubicBezierCurve::from_parameters(cur_pos, pos, params, &mut cur_pos, &mut cubic_control)?),
+            Command::SmoothCubicCurve(pos, params) => segments.append(&mut CubicBezierCurve::from_smooth_parameters(cur_pos, pos, params, &mut cur_pos, last_cubic_control, &mut cubic_control)?),
+            Command::EllipticalArc(pos, params) => segments.append(&mut EllipticalArc::from_parameters(cur_pos, pos, params, &mut cur_pos)?)
+        }
+
+        last_quadratic_control = quadratic_control;
+        last_cubic_control = cubic_control;
+    }
+
+    Ok(segments)
+}
+
+fn join_segments(paths: Vec<Vec<Box<dyn Segment>>>) -> Vec<Box<dyn Segment>> {
+    let mut segments: Vec<Box<dyn Segment>> = Vec::new();
+
+    let paths: Vec<_> = paths.into_iter().filter(|path| !path.is_empty()).collect();
+    let is_tour = paths.len() > 1;
+
+    for mut path in paths {
+        if let Some(last) = segments.last() {
+            let (end, start) = (last.get_point(1.0), path[0].get_point(0.0));
+            if end != start {
+                segments.push(Box::new(Line::new(end, start)));
             }
         }
+        segments.append(&mut path);
     }
 
-    if let Some(data) = path_data {
-        Ok(data.to_string())
+    if let (true, Some(first), Some(last)) = (is_tour, segments.first(), segments.last()) {
+        let (end, start) = (last.get_point(1.0), first.get_point(0.0));
+        if end != start {
+            segments.push(Box::new(Line::new(end, start)));
+        }
+    }
+
+    segments
+}
+
+fn check_parameters(command: char, pos: &Position, params: &Parameters, set_size: usize) -> Result<(), SvgLoadError> {
+    if params.is_empty() || !params.len().is_multiple_of(set_size) {
+        let command = match pos {
+            Position::Absolute => command,
+            Position::Relative => command.to_ascii_lowercase()
+        };
+        Err(SvgLoadError::WrongParameterCount(command, params.len()))
     } else {
-        Err(())
+        Ok(())
+    }
+}
+
+fn reflect_control(control: Option<Point>, about: Point) -> Point {
+    match control {
+        Some(control) => about.scale(2.0) - control,
+        None => about
+    }
+}
+
+impl FromStr for SVGPath {
+    type Err = SvgLoadError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        SVGPath::from_parser(Parser::new(s))
     }
 }
 
 impl ParametricPath for SVGPath {
     fn get_point(&self, t: f64) -> Point {
-        let scaled_t = t * self.segments.len() as f64;
-        let segment_index = scaled_t.floor() as usize;
-        let sub_t = scaled_t.fract();
+        match self.parameterization {
+            Parameterization::ArcLength if self.get_length() > 0.0
+                => self.get_index_point(self.arc_length_to_index_t(t)),
+            _ => self.get_index_point(t)
+        }
+    }
 
-        self.segments[segment_index].get_point(sub_t)
+    fn fourier_coefficient(&self, frequency: f64, samples: usize) -> Point {
+        if self.parameterization != Parameterization::SegmentIndex || self.segments.is_empty() {
+            return sampled_fourier_coefficient(self, frequency, samples);
+        }
+
+        // Segment i covers i/n <= t <= (i+1)/n, so its integral is a shifted, scaled copy
+        let n = self.segments.len() as f64;
+        // Only arcs are sampled, and sharing the samples out could leave each with just one
+        let segment_samples = ((samples as f64 / n).ceil() as usize).max(MIN_SEGMENT_SAMPLES);
+        let mut total = Point::ZERO;
+
+        for (i, segment) in self.segments.iter().enumerate() {
+            let shift = Point::from_ei(-2.0 * PI * frequency * i as f64 / n);
+            let integral = segment.fourier_coefficient(frequency / n, segment_samples);
+            total += shift * integral;
+        }
+
+        total / n
     }
 }
 
+trait Segment: ParametricPath {
+    fn transform(&mut self, transform: &Transform);
+}
+
 struct Line {
     start: Point,
     end: Point
@@ -93,8 +661,10 @@ impl Line {
         Self { start, end }
     }
 
-    fn from_horiz_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Vec<Box<dyn ParametricPath>> {
-        let mut return_vec: Vec<Box<dyn ParametricPath>> = Vec::new();
+    fn from_horiz_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('H', pos, params, 1)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
 
         let mut numbers = params.deref().iter().peekable();
 
@@ -112,11 +682,13 @@ impl Line {
 
         *tail = last_end;
 
-        return_vec
+        Ok(return_vec)
     }
 
-    fn from_vert_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Vec<Box<dyn ParametricPath>> {
-        let mut return_vec: Vec<Box<dyn ParametricPath>> = Vec::new();
+    fn from_vert_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('V', pos, params, 1)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
 
         let mut numbers = params.deref().iter().peekable();
 
@@ -134,11 +706,13 @@ impl Line {
 
         *tail = last_end;
 
-        return_vec
+        Ok(return_vec)
     }
 
-    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Vec<Box<dyn ParametricPath>> {
-        let mut return_vec: Vec<Box<dyn ParametricPath>> = Vec::new();
+    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('L', pos, params, 2)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
 
         let mut numbers = params.deref().iter().peekable();
 
@@ -159,13 +733,17 @@ impl Line {
 
         *tail = last_end;
 
-        return_vec
+        Ok(return_vec)
     }
 }
 
 impl ParametricPath for Line {
     fn get_point(&self, t: f64) -> Point {
-        self.start + (self.end - self.start).scale(t.fract())
+        self.start + (self.end - self.start).scale(t)
+    }
+
+    fn fourier_coefficient(&self, frequency: f64, _samples: usize) -> Point {
+        polynomial_fourier_integral(&[self.start, self.end - self.start], frequency)
     }
 }
 
@@ -176,12 +754,14 @@ struct QuadraticBezierCurve {
 }
 
 impl QuadraticBezierCurve {
-    fn new(start: Point, end: Point, control: Point) -> Self {
+    fn new(start: Point, control: Point, end: Point) -> Self {
         Self { start, control, end }
     }
 
-    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Vec<Box<dyn ParametricPath>> {
-        let mut return_vec: Vec<Box<dyn ParametricPath>> = Vec::new();
+    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point, last_control: &mut Option<Point>) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('Q', pos, params, 4)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
         let mut numbers = params.deref().iter().peekable();
         let mut last_end = start;
 
@@ -200,12 +780,49 @@ impl QuadraticBezierCurve {
                 end += start;
             }
             last_end = end;
+            *last_control = Some(control);
             return_vec.push(Box::new(Self::new(start, control, end)));
         }
 
         *tail = last_end;
 
-        return_vec
+        Ok(return_vec)
+    }
+
+    fn from_smooth_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point, prev_control: Option<Point>, last_control: &mut Option<Point>) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('T', pos, params, 2)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
+        let mut numbers = params.deref().iter().peekable();
+        let mut last_end = start;
+        let mut prev_control = prev_control;
+
+        while numbers.peek().is_some() {
+            let start = last_end;
+            let control = reflect_control(prev_control, start);
+            let mut end = Point::new(
+                *(numbers.next().unwrap()) as f64,
+                *(numbers.next().unwrap()) as f64
+            );
+            if pos == &Position::Relative {
+                end += start;
+            }
+            last_end = end;
+            prev_control = Some(control);
+            return_vec.push(Box::new(Self::new(start, control, end)));
+        }
+
+        *tail = last_end;
+        *last_control = prev_control;
+
+        Ok(return_vec)
+    }
+}
+
+impl Segment for Line {
+    fn transform(&mut self, transform: &Transform) {
+        self.start = transform.apply(self.start);
+        self.end = transform.apply(self.end);
     }
 }
 
@@ -215,6 +832,23 @@ impl ParametricPath for QuadraticBezierCurve {
         self.control.scale(2.0*t*(1.0-t)) +
         self.end.scale(t.powf(2.0))
     }
+
+    fn fourier_coefficient(&self, frequency: f64, _samples: usize) -> Point {
+        let coefficients = [
+            self.start,
+            (self.control - self.start).scale(2.0),
+            self.start - self.control.scale(2.0) + self.end
+        ];
+        polynomial_fourier_integral(&coefficients, frequency)
+    }
+}
+
+impl Segment for QuadraticBezierCurve {
+    fn transform(&mut self, transform: &Transform) {
+        self.start = transform.apply(self.start);
+        self.control = transform.apply(self.control);
+        self.end = transform.apply(self.end);
+    }
 }
 
 struct CubicBezierCurve {
@@ -229,8 +863,10 @@ impl CubicBezierCurve {
         Self { start, control1, control2, end }
     }
 
-    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Vec<Box<dyn ParametricPath>> {
-        let mut return_vec: Vec<Box<dyn ParametricPath>> = Vec::new();
+    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point, last_control: &mut Option<Point>) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('C', pos, params, 6)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
 
         let mut numbers = params.deref().iter().peekable();
 
@@ -256,12 +892,49 @@ impl CubicBezierCurve {
                 end += start;
             }
             last_end = end;
+            *last_control = Some(control2);
+            return_vec.push(Box::new(Self::new(start, control1, control2, end)));
+        }
+
+        *tail = last_end;
+
+        Ok(return_vec)
+    }
+
+    fn from_smooth_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point, prev_control: Option<Point>, last_control: &mut Option<Point>) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('S', pos, params, 4)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
+
+        let mut numbers = params.deref().iter().peekable();
+
+        let mut last_end = start;
+        let mut prev_control = prev_control;
+
+        while numbers.peek().is_some() {
+            let start = last_end;
+            let control1 = reflect_control(prev_control, start);
+            let mut control2 = Point::new(
+                *(numbers.next().unwrap()) as f64,
+                *(numbers.next().unwrap()) as f64
+            );
+            let mut end = Point::new(
+                *(numbers.next().unwrap()) as f64,
+                *(numbers.next().unwrap()) as f64
+            );
+            if pos == &Position::Relative {
+                control2 += start;
+                end += start;
+            }
+            last_end = end;
+            prev_control = Some(control2);
             return_vec.push(Box::new(Self::new(start, control1, control2, end)));
         }
 
         *tail = last_end;
+        *last_control = prev_control;
 
-        return_vec
+        Ok(return_vec)
     }
 }
 
@@ -272,24 +945,100 @@ impl ParametricPath for CubicBezierCurve {
         self.control2.scale( 3.0 * t*t * (1.0 - t) ) +
         self.end.scale( t*t*t )
     }
+
+    fn fourier_coefficient(&self, frequency: f64, _samples: usize) -> Point {
+        let coefficients = [
+            self.start,
+            (self.control1 - self.start).scale(3.0),
+            (self.start - self.control1.scale(2.0) + self.control2).scale(3.0),
+            self.end - self.start + (self.control1 - self.control2).scale(3.0)
+        ];
+        polynomial_fourier_integral(&coefficients, frequency)
+    }
+}
+
+impl Segment for CubicBezierCurve {
+    fn transform(&mut self, transform: &Transform) {
+        self.start = transform.apply(self.start);
+        self.control1 = transform.apply(self.control1);
+        self.control2 = transform.apply(self.control2);
+        self.end = transform.apply(self.end);
+    }
 }
 
 struct EllipticalArc {
     start: Point,
-    radii: Point,
-    x_angle: f64,
-    large_arc_flag: bool,
-    sweep_flag: bool,
-    end: Point
+    end: Point,
+    center: Point,
+    x_axis: Point,
+    y_axis: Point,
+    start_angle: f64,
+    sweep_angle: f64
 }
 
 impl EllipticalArc {
     fn new(start: Point, radii: Point, x_angle: f64, large_arc_flag: bool, sweep_flag: bool, end: Point) -> Self {
-        Self { start, radii, x_angle, large_arc_flag, sweep_flag, end }
+        let x_angle = x_angle.to_radians();
+        let mut radii = Point::new(radii.x.abs(), radii.y.abs());
+
+        if start == end || radii.x == 0.0 || radii.y == 0.0 {
+            return Self {
+                start, end, center: start, x_axis: Point::ZERO, y_axis: Point::ZERO,
+                start_angle: 0.0, sweep_angle: 0.0
+            };
+        }
+
+        // Endpoint to center parameterization, see SVG 1.1 appendix F.6.5
+        let mid = (start - end).scale(0.5).rotate(-x_angle);
+
+        let lambda = (mid.x / radii.x).powf(2.0) + (mid.y / radii.y).powf(2.0);
+        if lambda > 1.0 {
+            radii = radii.scale(lambda.sqrt());
+        }
+
+        let (rx2, ry2) = (radii.x.powf(2.0), radii.y.powf(2.0));
+        let (mx2, my2) = (mid.x.powf(2.0), mid.y.powf(2.0));
+        let mut coef = ((rx2*ry2 - rx2*my2 - ry2*mx2) / (rx2*my2 + ry2*mx2)).max(0.0).sqrt();
+        if large_arc_flag == sweep_flag {
+            coef = -coef;
+        }
+
+        let center_prime = Point::new(
+            coef * radii.x * mid.y / radii.y,
+            -coef * radii.y * mid.x / radii.x
+        );
+        let center = center_prime.rotate(x_angle) + (start + end).scale(0.5);
+
+        let start_angle = Point::new(
+            (mid.x - center_prime.x) / radii.x,
+            (mid.y - center_prime.y) / radii.y
+        ).angle();
+        let end_angle = Point::new(
+            (-mid.x - center_prime.x) / radii.x,
+            (-mid.y - center_prime.y) / radii.y
+        ).angle();
+
+        let mut sweep_angle = end_angle - start_angle;
+        if sweep_flag && sweep_angle < 0.0 {
+            sweep_angle += 2.0 * PI;
+        } else if !sweep_flag && sweep_angle > 0.0 {
+            sweep_angle -= 2.0 * PI;
+        }
+
+        let x_axis = Point::new(radii.x, 0.0).rotate(x_angle);
+        let y_axis = Point::new(0.0, radii.y).rotate(x_angle);
+
+        Self { start, end, center, x_axis, y_axis, start_angle, sweep_angle }
     }
 
-    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Vec<Box<dyn ParametricPath>> {
-        let mut return_vec: Vec<Box<dyn ParametricPath>> = Vec::new();
+    fn is_degenerate(&self) -> bool {
+        self.x_axis == Point::ZERO && self.y_axis == Point::ZERO
+    }
+
+    fn from_parameters(start: Point, pos: &Position, params: &Parameters, tail: &mut Point) -> Result<Vec<Box<dyn Segment>>, SvgLoadError> {
+        check_parameters('A', pos, params, 7)?;
+
+        let mut return_vec: Vec<Box<dyn Segment>> = Vec::new();
         let mut numbers = params.deref().iter().peekable();
         let mut last_end = start;
 
@@ -315,12 +1064,27 @@ impl EllipticalArc {
 
         *tail = last_end;
 
-        return_vec
+        Ok(return_vec)
     }
 }
 
 impl ParametricPath for EllipticalArc {
-    fn get_point(&self, t: f64) -> Point { // TODO: Implement Elliptical Arc
-        Line::new(self.start, self.end).get_point(t)
+    fn get_point(&self, t: f64) -> Point {
+        if self.is_degenerate() {
+            return Line::new(self.start, self.end).get_point(t);
+        }
+
+        let angle = self.start_angle + self.sweep_angle * t;
+        self.center + self.x_axis.scale(angle.cos()) + self.y_axis.scale(angle.sin())
+    }
+}
+
+impl Segment for EllipticalArc {
+    fn transform(&mut self, transform: &Transform) {
+        self.start = transform.apply(self.start);
+        self.end = transform.apply(self.end);
+        self.center = transform.apply(self.center);
+        self.x_axis = transform.apply_vector(self.x_axis);
+        self.y_axis = transform.apply_vector(self.y_axis);
     }
 }
\ No newline at end of file
@@ -0,0 +1,99 @@
+use super::common::Point;
+
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct Transform {
+    a: f64, b: f64,
+    c: f64, d: f64,
+    e: f64, f: f64
+}
+
+impl Transform {
+    pub const IDENTITY: Transform = Transform { a: 1.0, b: 0.0, c: 0.0, d: 1.0, e: 0.0, f: 0.0 };
+
+    pub fn new(a: f64, b: f64, c: f64, d: f64, e: f64, f: f64) -> Self {
+        Self { a, b, c, d, e, f }
+    }
+
+    pub fn translate(x: f64, y: f64) -> Self {
+        Self::new(1.0, 0.0, 0.0, 1.0, x, y)
+    }
+
+    pub fn scale(x: f64, y: f64) -> Self {
+        Self::new(x, 0.0, 0.0, y, 0.0, 0.0)
+    }
+
+    pub fn rotate(angle: f64) -> Self {
+        let (sin, cos) = angle.to_radians().sin_cos();
+        Self::new(cos, sin, -sin, cos, 0.0, 0.0)
+    }
+
+    pub fn rotate_about(angle: f64, center: Point) -> Self {
+        Self::translate(center.x, center.y)
+            .multiply(&Self::rotate(angle))
+            .multiply(&Self::translate(-center.x, -center.y))
+    }
+
+    pub fn skew_x(angle: f64) -> Self {
+        Self::new(1.0, 0.0, angle.to_radians().tan(), 1.0, 0.0, 0.0)
+    }
+
+    pub fn skew_y(angle: f64) -> Self {
+        Self::new(1.0, angle.to_radians().tan(), 0.0, 1.0, 0.0, 0.0)
+    }
+
+    // Parses the value of an SVG `transform` attribute, e.g. "translate(10, 20) rotate(45)"
+    pub fn parse(value: &str) -> Option<Self> {
+        let mut transform = Self::IDENTITY;
+
+        for function in value.split(')').map(str::trim).filter(|function| !function.is_empty()) {
+            let mut parts = function.splitn(2, '(');
+            let name = parts.next()?.trim_matches(|c: char| c.is_whitespace() || c == ',');
+            let args = parts.next()?
+                .split(|c: char| c.is_whitespace() || c == ',')
+                .filter(|arg| !arg.is_empty())
+                .map(str::parse)
+                .collect::<Result<Vec<f64>, _>>()
+                .ok()?;
+
+            let next = match (name, args.as_slice()) {
+                ("matrix", &[a, b, c, d, e, f]) => Self::new(a, b, c, d, e, f),
+                ("translate", &[x]) => Self::translate(x, 0.0),
+                ("translate", &[x, y]) => Self::translate(x, y),
+                ("scale", &[s]) => Self::scale(s, s),
+                ("scale", &[x, y]) => Self::scale(x, y),
+                ("rotate", &[angle]) => Self::rotate(angle),
+                ("rotate", &[angle, x, y]) => Self::rotate_about(angle, Point::new(x, y)),
+                ("skewX", &[angle]) => Self::skew_x(angle),
+                ("skewY", &[angle]) => Self::skew_y(angle),
+                _ => return None
+            };
+
+            transform = transform.multiply(&next);
+        }
+
+        Some(transform)
+    }
+
+    // Matrix product self * inner, so `inner` is applied first
+    pub fn multiply(&self, inner: &Transform) -> Self {
+        Self::new(
+            self.a * inner.a + self.c * inner.b,
+            self.b * inner.a + self.d * inner.b,
+            self.a * inner.c + self.c * inner.d,
+            self.b * inner.c + self.d * inner.d,
+            self.a * inner.e + self.c * inner.f + self.e,
+            self.b * inner.e + self.d * inner.f + self.f
+        )
+    }
+
+    pub fn apply(&self, point: Point) -> Point {
+        self.apply_vector(point) + (self.e, self.f)
+    }
+
+    pub fn apply_vector(&self, vector: Point) -> Point {
+        Point::new(
+            self.a * vector.x + self.c * vector.y,
+            self.b * vector.x + self.d * vector.y
+        )
+    }
+}
@@ -14,6 +14,18 @@ impl SVector {
         Self { start_angle, frequency, magnitude }
     }
 
+    pub fn get_start_angle(&self) -> f64 {
+        self.start_angle
+    }
+
+    pub fn get_frequency(&self) -> f64 {
+        self.frequency
+    }
+
+    pub fn get_magnitude(&self) -> f64 {
+        self.magnitude
+    }
+
     pub fn get_state(&self, t: f64) -> Point {
         let angle = self.start_angle + 2.0 * PI * self.frequency * t;
         let x = self.magnitude * angle.cos();
@@ -31,9 +43,22 @@ impl World {
         Self { svectors }
     }
 
-    pub fn get_state(&self, t: f64) -> WorldStateIter {
+    pub fn get_svectors(&self) -> &[SVector] {
+        &self.svectors
+    }
+
+    pub fn get_state(&self, t: f64) -> WorldStateIter<'_> {
         WorldStateIter::new(self.svectors.iter(), t)
     }
+
+    pub fn tip(&self, t: f64) -> Point {
+        self.get_state(t).sum()
+    }
+
+    // Samples one full period of the reconstructed path
+    pub fn sample_path(&self, n: usize) -> Vec<Point> {
+        (0..n).map(|i| self.tip(i as f64 / n as f64)).collect()
+    }
 }
 
 pub struct WorldStateIter<'a> {
@@ -0,0 +1,81 @@
+use fourier_series::clock::SimulationClock;
+use fourier_series::raster::RasterRenderer;
+
+mod common;
+
+#[test]
+fn advances_by_scaled_time() {
+    let mut clock = SimulationClock::new(0.5);
+    clock.advance(1.0);
+    clock.advance(0.5);
+    assert_eq!(clock.get_time(), 0.75);
+}
+
+#[test]
+fn speed_changes_do_not_jump() {
+    let mut clock = SimulationClock::new(0.1);
+    clock.advance(2.0);
+    let before = clock.get_time();
+
+    clock.set_time_scale(1.0);
+    assert_eq!(clock.get_time(), before);
+    clock.advance(0.5);
+    assert!((clock.get_time() - (before + 0.5)).abs() < 1e-12);
+}
+
+#[test]
+fn pausing_stops_time_but_not_stepping() {
+    let mut clock = SimulationClock::new(1.0);
+    clock.advance(0.25);
+    clock.set_paused(true);
+    clock.advance(10.0);
+    assert_eq!(clock.get_time(), 0.25);
+
+    clock.step(0.5);
+    assert_eq!(clock.get_time(), 0.75);
+    clock.step(-0.25);
+    assert_eq!(clock.get_time(), 0.5);
+
+    clock.toggle_paused();
+    clock.advance(0.5);
+    assert_eq!(clock.get_time(), 1.0);
+}
+
+#[test]
+fn seek_sets_time() {
+    let mut clock = SimulationClock::new(0.1);
+    clock.advance(3.0);
+    clock.seek(0.6);
+    assert_eq!(clock.get_time(), 0.6);
+}
+
+#[test]
+fn paused_frames_are_identical() {
+    let mut fs = common::example_builder()
+        .start_paused(true)
+        .build_with_renderer(RasterRenderer::new(80, 60))
+        .unwrap();
+
+    let t = fs.get_clock_mut().advance(1.0);
+    assert_eq!(t, 0.0);
+    fs.draw_frame(t).unwrap();
+    let first = fs.get_renderer().get_pixels().to_vec();
+    let t = fs.get_clock_mut().advance(1.0);
+    fs.draw_frame(t).unwrap();
+    assert_eq!(fs.get_renderer().get_pixels(), first.as_slice());
+}
+
+#[test]
+fn stepping_back_rewinds_the_trail() {
+    let mut fs = common::example_series(80, 60);
+
+    for frame in 0..5 {
+        fs.draw_frame(frame as f64 / 20.0).unwrap();
+    }
+    let expected = fs.get_renderer().get_pixels().to_vec();
+
+    fs.draw_frame(0.25).unwrap();
+    fs.draw_frame(0.3).unwrap();
+    fs.draw_frame(0.2).unwrap();
+    assert_eq!(fs.get_renderer().get_pixels(), expected.as_slice());
+}
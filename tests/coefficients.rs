@@ -0,0 +1,75 @@
+use fourier_series::coefficients::{CoefficientFile, CoefficientFileError, CoefficientFormat};
+use fourier_series::fourier::FourierConfig;
+use fourier_series::{load_coefficients, FourierSeriesConfig};
+
+fn example_file() -> CoefficientFile {
+    let config = FourierSeriesConfig {
+        fourier: FourierConfig { max_frequency: 5, samples: 1024, ..FourierConfig::default() },
+        ..FourierSeriesConfig::default()
+    };
+    load_coefficients(&config).unwrap()
+}
+
+fn round_trip(file: &CoefficientFile, format: CoefficientFormat) -> CoefficientFile {
+    let mut buffer = Vec::new();
+    file.write(&mut buffer, format).unwrap();
+    CoefficientFile::read(buffer.as_slice(), format).unwrap()
+}
+
+#[test]
+fn metadata_is_recorded() {
+    let file = example_file();
+
+    assert_eq!(file.coefficients.len(), 11);
+    assert_eq!(file.metadata.source.as_deref(), Some("example.svg"));
+    assert_eq!(file.metadata.samples, Some(1024));
+    assert_eq!(file.metadata.max_frequency, Some(5));
+}
+
+#[test]
+fn json_round_trip() {
+    let file = example_file();
+    assert_eq!(round_trip(&file, CoefficientFormat::Json), file);
+}
+
+#[test]
+fn csv_round_trip() {
+    let file = example_file();
+    assert_eq!(round_trip(&file, CoefficientFormat::Csv), file);
+}
+
+#[test]
+fn world_from_file_matches() {
+    let file = example_file();
+    let world = file.to_world();
+    let loaded = round_trip(&file, CoefficientFormat::Csv).to_world();
+
+    for i in 0..50 {
+        let t = i as f64 / 50.0;
+        assert!(world.tip(t).approx_eq(loaded.tip(t), 1e-12));
+    }
+}
+
+#[test]
+fn hand_written_csv() {
+    let csv = "frequency,magnitude,start_angle\n0,2,0\n1,1,0\n";
+    let file = CoefficientFile::read(csv.as_bytes(), CoefficientFormat::Csv).unwrap();
+    assert_eq!(file.metadata.source, None);
+
+    let world = file.to_world();
+    assert!(world.tip(0.0).approx_eq((3.0, 0.0), 1e-12));
+    assert!(world.tip(0.25).approx_eq((2.0, 1.0), 1e-12));
+
+    let bad = "frequency,magnitude,start_angle\n1,2\n";
+    match CoefficientFile::read(bad.as_bytes(), CoefficientFormat::Csv) {
+        Err(CoefficientFileError::InvalidCsvLine(2, _)) => {},
+        other => panic!("expected an invalid line error, got {:?}", other)
+    }
+}
+
+#[test]
+fn format_from_extension() {
+    assert_eq!(CoefficientFormat::from_path("out.json"), Some(CoefficientFormat::Json));
+    assert_eq!(CoefficientFormat::from_path("out.CSV"), Some(CoefficientFormat::Csv));
+    assert_eq!(CoefficientFormat::from_path("drawing.svg"), None);
+}
@@ -0,0 +1,19 @@
+// Not every test file uses every helper
+#![allow(dead_code)]
+
+use fourier_series::raster::RasterRenderer;
+use fourier_series::{FourierSeries, FourierSeriesBuilder};
+
+// example.svg with few enough terms to keep the tests quick
+pub fn example_builder() -> FourierSeriesBuilder {
+    FourierSeriesBuilder::new()
+        .source("example.svg")
+        .max_frequency(10)
+        .samples(1024)
+}
+
+pub fn example_series(width: u32, height: u32) -> FourierSeries<RasterRenderer> {
+    example_builder()
+        .build_with_renderer(RasterRenderer::new(width, height))
+        .unwrap()
+}
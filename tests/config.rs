@@ -0,0 +1,87 @@
+use fourier_series::fourier::{CoefficientMethod, TermSelection};
+use fourier_series::svgpath::Parameterization;
+use fourier_series::{ConfigError, FourierSeriesBuilder, FourierSeriesConfig, FourierSeriesError};
+
+#[test]
+fn default_config_is_valid() {
+    assert_eq!(FourierSeriesConfig::default().validate(), Ok(()));
+}
+
+#[test]
+fn builder_sets_options() {
+    let builder = FourierSeriesBuilder::new()
+        .source("drawing.svg")
+        .window_size(1280, 720)
+        .samples(2048)
+        .terms(TermSelection::Count(20))
+        .time_scale(0.5)
+        .trail_length(250);
+    let config = builder.get_config();
+
+    assert_eq!(config.input.to_str(), Some("drawing.svg"));
+    assert_eq!((config.width, config.height), (1280, 720));
+    assert_eq!(config.fourier.samples, 2048);
+    assert_eq!(config.fourier.terms, TermSelection::Count(20));
+    assert_eq!(config.time_scale, 0.5);
+    assert_eq!(config.trail_length, 250);
+    assert_eq!(config.validate(), Ok(()));
+}
+
+#[test]
+fn invalid_options_are_rejected() {
+    let validate = |builder: FourierSeriesBuilder| builder.get_config().validate();
+
+    assert_eq!(validate(FourierSeriesBuilder::new().window_size(0, 600)), Err(ConfigError::InvalidWindowSize(0, 600)));
+    assert_eq!(validate(FourierSeriesBuilder::new().time_scale(0.0)), Err(ConfigError::InvalidTimeScale(0.0)));
+    assert_eq!(validate(FourierSeriesBuilder::new().frame_rate(-1.0)), Err(ConfigError::InvalidFrameRate(-1.0)));
+    assert_eq!(validate(FourierSeriesBuilder::new().samples(0)), Err(ConfigError::NoSamples));
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().max_frequency(50).samples(100)),
+        Err(ConfigError::TooFewSamples(100, 50))
+    );
+    assert_eq!(validate(FourierSeriesBuilder::new().max_frequency(50).samples(101)), Ok(()));
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().coefficient_method(CoefficientMethod::Exact)),
+        Err(ConfigError::ExactWithArcLength)
+    );
+    assert_eq!(
+        validate(FourierSeriesBuilder::new()
+            .coefficient_method(CoefficientMethod::Exact)
+            .parameterization(Parameterization::SegmentIndex)),
+        Ok(())
+    );
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().terms(TermSelection::Count(0))),
+        Err(ConfigError::InvalidTermSelection(TermSelection::Count(0)))
+    );
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().terms(TermSelection::EnergyFraction(0.0))),
+        Err(ConfigError::InvalidTermSelection(TermSelection::EnergyFraction(0.0)))
+    );
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().terms(TermSelection::EnergyFraction(1.5))),
+        Err(ConfigError::InvalidTermSelection(TermSelection::EnergyFraction(1.5)))
+    );
+}
+
+#[test]
+fn coefficient_files_skip_fourier_options() {
+    let validate = |builder: FourierSeriesBuilder| builder.get_config().validate();
+
+    assert_eq!(validate(FourierSeriesBuilder::new().source("saved.json").samples(100)), Ok(()));
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().source("saved.csv").coefficient_method(CoefficientMethod::Exact)),
+        Ok(())
+    );
+    assert_eq!(
+        validate(FourierSeriesBuilder::new().source("saved.json").window_size(0, 0)),
+        Err(ConfigError::InvalidWindowSize(0, 0))
+    );
+}
+
+#[test]
+fn errors_are_displayed() {
+    let error = FourierSeriesBuilder::new().max_frequency(50).samples(100).get_config().validate().unwrap_err();
+    assert_eq!(error.to_string(), "100 samples cannot resolve frequency 50, use more than 100");
+    assert_eq!(FourierSeriesError::from(error).to_string(), "100 samples cannot resolve frequency 50, use more than 100");
+}
@@ -0,0 +1,120 @@
+use std::env;
+use std::fs;
+use std::path::Path;
+use std::process;
+
+use fourier_series::export::{export_gif, export_png_frames, export_raw_frames, write_svg, ExportOptions, SvgExportOptions};
+use fourier_series::path::ParametricPath;
+use fourier_series::svgpath::{Normalization, Origin, SVGPath};
+use fourier_series::world::{SVector, World};
+
+mod common;
+
+#[test]
+fn gif_has_one_frame_per_step() {
+    let mut fs = common::example_series(120, 80);
+
+    let mut gif = Vec::new();
+    let options = ExportOptions { frames: 12, frame_rate: 25.0, ..ExportOptions::default() };
+    export_gif(&mut fs, &mut gif, options).unwrap();
+
+    let mut decoder = gif::DecodeOptions::new().read_info(gif.as_slice()).unwrap();
+    assert_eq!((decoder.width(), decoder.height()), (120, 80));
+
+    let mut frames = 0;
+    while let Some(frame) = decoder.read_next_frame().unwrap() {
+        assert_eq!((frame.width, frame.height), (120, 80));
+        assert_eq!(frame.delay, 4);
+        frames += 1;
+    }
+    assert_eq!(frames, 12);
+}
+
+#[test]
+fn raw_frames_are_deterministic() {
+    let render = || {
+        let mut fs = common::example_series(64, 48);
+
+        let mut raw = Vec::new();
+        let options = ExportOptions { frames: 5, frame_rate: 30.0, ..ExportOptions::default() };
+        export_raw_frames(&mut fs, &mut raw, options).unwrap();
+        raw
+    };
+
+    let raw = render();
+    assert_eq!(raw.len(), 5 * 64 * 48 * 3);
+    assert_eq!(raw, render());
+}
+
+fn read_frames(directory: &Path) -> Vec<(String, Vec<u8>)> {
+    let mut frames: Vec<(String, Vec<u8>)> = fs::read_dir(directory).unwrap()
+        .map(|entry| entry.unwrap().path())
+        .map(|path| (path.file_name().unwrap().to_str().unwrap().to_string(), fs::read(&path).unwrap()))
+        .collect();
+    frames.sort();
+    frames
+}
+
+#[test]
+fn png_frames_are_numbered_and_deterministic() {
+    let render = |name: &str| {
+        let directory = env::temp_dir().join(format!("fourier-series-{}-{}", name, process::id()));
+        let mut series = common::example_series(64, 48);
+
+        let options = ExportOptions { frames: 3, frame_rate: 30.0, ..ExportOptions::default() };
+        export_png_frames(&mut series, &directory, options).unwrap();
+        let frames = read_frames(&directory);
+        fs::remove_dir_all(&directory).unwrap();
+        frames
+    };
+
+    let frames = render("png-a");
+    let names: Vec<&str> = frames.iter().map(|(name, _)| name.as_str()).collect();
+    assert_eq!(names, ["frame_00000.png", "frame_00001.png", "frame_00002.png"]);
+
+    for (_, bytes) in frames.iter() {
+        let reader = png::Decoder::new(bytes.as_slice()).read_info().unwrap();
+        assert_eq!((reader.info().width, reader.info().height), (64, 48));
+    }
+    assert_eq!(frames, render("png-b"));
+}
+
+fn ellipse_world() -> World {
+    World::new(vec![
+        SVector::new(0.5, 0.0, 2.0),
+        SVector::new(0.0, 1.0, 3.0),
+        SVector::new(1.0, -1.0, 1.0),
+        SVector::new(0.3, 2.0, 0.5)
+    ])
+}
+
+#[test]
+fn svg_path_matches_reconstruction() {
+    let world = ellipse_world();
+    let options = SvgExportOptions { samples: 200, ..SvgExportOptions::default() };
+
+    let mut svg = Vec::new();
+    write_svg(&world, &mut svg, options).unwrap();
+
+    let mut path: SVGPath = String::from_utf8(svg).unwrap().parse().unwrap();
+    path.normalize(Normalization { flip_y: true, origin: Origin::Unchanged, fit_to: None });
+
+    for i in 0..200 {
+        let t = i as f64 / 200.0;
+        assert!(path.get_point(t).approx_eq(world.tip(t), 1e-4), "t = {}", t);
+    }
+}
+
+#[test]
+fn svg_includes_epicycles() {
+    let world = ellipse_world();
+    let options = SvgExportOptions { epicycles_at: Some(0.25), ..SvgExportOptions::default() };
+
+    let mut svg = Vec::new();
+    write_svg(&world, &mut svg, options).unwrap();
+    let svg = String::from_utf8(svg).unwrap();
+
+    // The constant term gets an arm but no circle
+    assert_eq!(svg.matches("<circle").count(), 3);
+    assert_eq!(svg.matches("<line").count(), 4);
+}
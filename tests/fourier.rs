@@ -0,0 +1,185 @@
+use fourier_series::common::Point;
+use fourier_series::fourier::{
+    coefficient, compute_coefficients, hausdorff_error, rms_error,
+    CoefficientMethod, CoefficientOrder, FourierConfig, TermSelection
+};
+use fourier_series::path::ParametricPath;
+use fourier_series::svgpath::{Parameterization, SVGPath};
+use fourier_series::world::World;
+
+fn reconstruct(data: &str, config: FourierConfig) -> World {
+    let mut path = SVGPath::from_path_data(data).unwrap();
+    path.set_parameterization(Parameterization::ArcLength);
+    World::new(compute_coefficients(&path, config))
+}
+
+#[test]
+fn dc_term_reproduces_translation() {
+    let config = FourierConfig { max_frequency: 10, samples: 1000, ..FourierConfig::default() };
+    let original = reconstruct("M 0 0 L 4 0 L 4 4 L 0 4 Z", config);
+    let translated = reconstruct("M 10 -20 L 14 -20 L 14 -16 L 10 -16 Z", config);
+
+    for i in 0..20 {
+        let t = i as f64 / 20.0;
+        let offset = translated.tip(t) - original.tip(t);
+        assert!(offset.approx_eq((10.0, -20.0), 1e-6), "offset was {:?}", offset);
+    }
+}
+
+#[test]
+fn dc_term_is_path_centroid() {
+    let config = FourierConfig { max_frequency: 0, samples: 1000, ..FourierConfig::default() };
+    let world = reconstruct("M 1 1 L 3 1 L 3 3 L 1 3 Z", config);
+
+    assert!(world.tip(0.3).approx_eq((2.0, 2.0), 1e-9));
+}
+
+fn assert_same_coefficients(data: &str, samples: usize) {
+    let mut path = SVGPath::from_path_data(data).unwrap();
+    path.set_parameterization(Parameterization::ArcLength);
+
+    let config = FourierConfig { max_frequency: 20, samples, ..FourierConfig::default() };
+    let fft = compute_coefficients(&path, FourierConfig { method: CoefficientMethod::Fft, ..config });
+    let average = compute_coefficients(&path, FourierConfig { method: CoefficientMethod::Average, ..config });
+
+    assert_eq!(fft.len(), average.len());
+    for (a, b) in fft.iter().zip(average.iter()) {
+        assert_eq!(a.get_frequency(), b.get_frequency());
+        let difference = Point::from_ei(a.get_start_angle()) * a.get_magnitude()
+            - Point::from_ei(b.get_start_angle()) * b.get_magnitude();
+        assert!(difference.mag() < 1e-9, "frequency {} differs by {:?}", a.get_frequency(), difference);
+    }
+}
+
+#[test]
+fn fft_matches_average_for_power_of_two_samples() {
+    assert_same_coefficients("M 0 0 C 3 4 5 -2 6 0 Q 8 3 4 5 A 2 3 30 0 1 0 0", 1024);
+}
+
+#[test]
+fn fft_matches_average_for_mixed_radix_samples() {
+    assert_same_coefficients("M 0 0 C 3 4 5 -2 6 0 Q 8 3 4 5 A 2 3 30 0 1 0 0", 1000);
+    assert_same_coefficients("M 0 0 L 4 0 L 4 4 L 0 4 Z", 997);
+}
+
+#[test]
+fn fft_matches_average_for_large_prime_factors() {
+    assert_same_coefficients("M 0 0 C 3 4 5 -2 6 0 Q 8 3 4 5 A 2 3 30 0 1 0 0", 10007);
+    assert_same_coefficients("M 0 0 L 4 0 L 4 4 L 0 4 Z", 2 * 3 * 1009);
+    assert_same_coefficients("M 0 0 L 4 0 L 4 4 L 0 4 Z", 7 * 29);
+}
+
+#[test]
+fn exact_coefficients_match_dense_sampling() {
+    let path = SVGPath::from_path_data("M 0 0 C 3 4 5 -2 6 0 Q 8 3 4 5 L 1 2 Z").unwrap();
+
+    for &frequency in [-7, -1, 0, 1, 3, 12].iter() {
+        let exact = path.fourier_coefficient(frequency as f64, 0);
+        let sampled = coefficient(&path, frequency, 200_000);
+        assert!(exact.approx_eq(sampled, 1e-6), "frequency {}: {:?} vs {:?}", frequency, exact, sampled);
+    }
+}
+
+#[test]
+fn exact_coefficients_sample_arcs_densely() {
+    let path = SVGPath::from_path_data("M 0 0 L 1 0 L 2 0 L 3 0 A 2 1 0 0 1 0 0 Z").unwrap();
+
+    for &frequency in [-3, 0, 1, 5].iter() {
+        let exact = path.fourier_coefficient(frequency as f64, 1);
+        let sampled = coefficient(&path, frequency, 200_000);
+        assert!(exact.approx_eq(sampled, 1e-3), "frequency {}: {:?} vs {:?}", frequency, exact, sampled);
+    }
+}
+
+fn square_at(x: f64, y: f64) -> SVGPath {
+    let data = format!("M {0} {1} L {2} {1} L {2} {3} L {0} {3} Z", x, y, x + 4.0, y + 4.0);
+    let mut path = SVGPath::from_path_data(&data).unwrap();
+    path.set_parameterization(Parameterization::ArcLength);
+    path
+}
+
+fn selection_config() -> FourierConfig {
+    FourierConfig {
+        max_frequency: 20,
+        samples: 1000,
+        order: CoefficientOrder::Magnitude,
+        ..FourierConfig::default()
+    }
+}
+
+#[test]
+fn empty_term_selection_keeps_dc_term() {
+    let path = square_at(0.0, 0.0);
+    let config = FourierConfig { max_frequency: 10, samples: 1000, ..FourierConfig::default() };
+
+    for &terms in [TermSelection::Count(0), TermSelection::EnergyFraction(0.0), TermSelection::RmsError(1e6)].iter() {
+        let svectors = compute_coefficients(&path, FourierConfig { terms, ..config });
+        assert_eq!(svectors.len(), 1, "{:?}", terms);
+        assert_eq!(svectors[0].get_frequency(), 0.0);
+        assert!(World::new(svectors).tip(0.3).approx_eq((2.0, 2.0), 1e-9));
+    }
+}
+
+#[test]
+fn count_does_not_include_dc_term() {
+    let svectors = compute_coefficients(&square_at(1000.0, 1000.0), FourierConfig {
+        terms: TermSelection::Count(1),
+        ..selection_config()
+    });
+
+    assert_eq!(svectors.len(), 2);
+    assert_eq!(svectors[0].get_frequency(), 0.0);
+    assert_eq!(svectors[1].get_frequency().abs(), 1.0);
+}
+
+#[test]
+fn energy_fraction_selects_fewest_terms() {
+    for &(x, y) in [(0.0, 0.0), (1000.0, 1000.0)].iter() {
+        let path = square_at(x, y);
+        // Everything after the constant term, largest first
+        let energies: Vec<f64> = compute_coefficients(&path, selection_config())[1..].iter()
+            .map(|svector| svector.get_magnitude().powi(2))
+            .collect();
+        let total: f64 = energies.iter().sum();
+
+        for &fraction in [0.5, 0.9, 0.999].iter() {
+            let terms = TermSelection::EnergyFraction(fraction);
+            let count = compute_coefficients(&path, FourierConfig { terms, ..selection_config() }).len() - 1;
+            let kept: f64 = energies[..count].iter().sum();
+            let without_last: f64 = energies[..count - 1].iter().sum();
+
+            assert!(kept >= fraction * total, "{} terms hold too little energy for {}", count, fraction);
+            assert!(without_last < fraction * total, "{} terms are more than needed for {}", count, fraction);
+        }
+    }
+}
+
+#[test]
+fn rms_error_selects_fewest_terms_within_error() {
+    for &(x, y) in [(0.0, 0.0), (1000.0, 1000.0)].iter() {
+        let path = square_at(x, y);
+
+        for &error in [0.5, 0.2, 0.1].iter() {
+            let terms = TermSelection::RmsError(error);
+            let svectors = compute_coefficients(&path, FourierConfig { terms, ..selection_config() });
+            let terms = TermSelection::Count(svectors.len() - 2);
+            let fewer = compute_coefficients(&path, FourierConfig { terms, ..selection_config() });
+
+            assert!(rms_error(&path, &World::new(svectors), 1000) <= error);
+            assert!(rms_error(&path, &World::new(fewer), 1000) > error);
+        }
+    }
+}
+
+#[test]
+fn hausdorff_error_is_largest_distance_between_paths() {
+    let path = square_at(0.0, 0.0);
+    let config = FourierConfig { max_frequency: 0, samples: 1000, ..FourierConfig::default() };
+
+    // Only the centroid is left, so the corners are furthest from it
+    let centroid = World::new(compute_coefficients(&path, config));
+    assert!((hausdorff_error(&path, &centroid, 1000) - 8.0f64.sqrt()).abs() < 1e-9);
+
+    let close = World::new(compute_coefficients(&path, FourierConfig { max_frequency: 100, ..config }));
+    assert!(hausdorff_error(&path, &close, 1000) < 0.1);
+}
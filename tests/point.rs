@@ -0,0 +1,52 @@
+use std::f64::consts::PI;
+
+use fourier_series::common::Point;
+
+const EPSILON: f64 = 1e-12;
+
+#[test]
+fn division_undoes_multiplication() {
+    let (a, b) = (Point::new(3.0, -2.0), Point::new(0.5, 4.0));
+
+    assert!(((a * b) / b).approx_eq(a, EPSILON));
+    assert!((a * b.recip()).approx_eq(a / b, EPSILON));
+    assert!((b * b.recip()).approx_eq((1.0, 0.0), EPSILON));
+}
+
+#[test]
+fn multiplication_by_i_rotates_a_quarter_turn() {
+    assert!((Point::new(2.0, 1.0) * Point::I).approx_eq((-1.0, 2.0), EPSILON));
+    assert!((Point::I * Point::I).approx_eq((-1.0, 0.0), EPSILON));
+}
+
+#[test]
+fn exp_follows_eulers_formula() {
+    assert!((Point::I * PI).exp().approx_eq((-1.0, 0.0), EPSILON));
+    assert!(Point::new(1.0, 0.0).exp().approx_eq((1.0f64.exp(), 0.0), EPSILON));
+    assert!(Point::new(2.0, PI / 2.0).exp().approx_eq((0.0, 2.0f64.exp()), EPSILON));
+}
+
+#[test]
+fn conj_negates_imaginary_part() {
+    let a = Point::new(3.0, -2.0);
+
+    assert_eq!(a.conj(), Point::new(3.0, 2.0));
+    assert!((a * a.conj()).approx_eq((a.abs_sq(), 0.0), EPSILON));
+}
+
+#[test]
+fn sum_adds_owned_and_borrowed_points() {
+    let points = vec![Point::new(1.0, 2.0), Point::new(-3.0, 0.5), Point::new(0.25, 0.25)];
+
+    assert_eq!(points.iter().sum::<Point>(), Point::new(-1.75, 2.75));
+    assert_eq!(points.into_iter().sum::<Point>(), Point::new(-1.75, 2.75));
+    assert_eq!(Vec::<Point>::new().into_iter().sum::<Point>(), Point::ZERO);
+}
+
+#[test]
+#[allow(deprecated)]
+fn complex_mult_matches_multiplication() {
+    let (a, b) = (Point::new(3.0, -2.0), Point::new(0.5, 4.0));
+
+    assert_eq!(Point::complex_mult(a, b), a * b);
+}
@@ -0,0 +1,78 @@
+use fourier_series::color::Color;
+use fourier_series::common::Point;
+use fourier_series::raster::RasterRenderer;
+use fourier_series::renderer::{Camera, Renderer};
+
+mod common;
+
+fn unit_renderer(width: u32, height: u32) -> RasterRenderer {
+    let mut renderer = RasterRenderer::new(width, height);
+    *renderer.get_camera_mut() = Camera::new(Point::ZERO, 1.0);
+    renderer
+}
+
+#[test]
+fn clear_fills_every_pixel() {
+    let mut renderer = unit_renderer(4, 3);
+    renderer.set_color(Color::RGB(10, 20, 30));
+    renderer.clear();
+
+    assert_eq!(renderer.get_pixels().len(), 4 * 3 * 4);
+    assert!(renderer.get_pixels().chunks(4).all(|pixel| pixel == [10, 20, 30, 255]));
+}
+
+#[test]
+fn lines_are_drawn_with_y_up() {
+    let mut renderer = unit_renderer(10, 10);
+    renderer.set_color(Color::RGB(0, 0, 0));
+    renderer.clear();
+    renderer.set_color(Color::RGB(255, 0, 0));
+    renderer.draw_line(Point::new(-5.0, 2.0), Point::new(5.0, 2.0)).unwrap();
+
+    let red = Color::RGB(255, 0, 0);
+    let black = Color::RGB(0, 0, 0);
+    // The screen centre is pixel (5, 5), so y = 2 lands on row 3
+    for x in 0..10 {
+        assert_eq!(renderer.get_pixel(x, 3), Some(red));
+        assert_eq!(renderer.get_pixel(x, 7), Some(black));
+    }
+}
+
+#[test]
+fn lines_off_screen_are_clipped() {
+    let mut renderer = unit_renderer(8, 8);
+    renderer.set_color(Color::RGB(0, 0, 0));
+    renderer.clear();
+    renderer.set_color(Color::RGB(255, 255, 255));
+    renderer.draw_line(Point::new(-1e9, 0.0), Point::new(1e9, 0.0)).unwrap();
+    renderer.draw_line(Point::new(100.0, 100.0), Point::new(200.0, 100.0)).unwrap();
+
+    let lit = renderer.get_pixels().chunks(4).filter(|pixel| pixel[0] == 255).count();
+    assert_eq!(lit, 8);
+}
+
+#[test]
+fn zoom_keeps_point_under_cursor() {
+    let mut renderer = unit_renderer(100, 100);
+    let size = renderer.get_size();
+    let before = renderer.get_camera().pixel_to_point(20, 30, size);
+    renderer.zoom(1.5, 20, 30);
+    let after = renderer.get_camera().pixel_to_point(20, 30, size);
+
+    assert!(before.approx_eq(after, 1e-9));
+}
+
+#[test]
+fn fourier_series_renders_headlessly() {
+    let mut fs = common::example_builder()
+        .background(Color::RGB(0, 0, 0))
+        .build_with_renderer(RasterRenderer::new(200, 150))
+        .unwrap();
+
+    for frame in 0..10 {
+        fs.draw_frame(frame as f64 / 10.0).unwrap();
+    }
+
+    let lit = fs.get_renderer().get_pixels().chunks(4).filter(|pixel| pixel[..3] != [0, 0, 0]).count();
+    assert!(lit > 0);
+}
@@ -0,0 +1,415 @@
+use std::env;
+use std::f64::consts::PI;
+use std::fs;
+use std::path::PathBuf;
+use std::process;
+
+use fourier_series::common::Point;
+use fourier_series::path::ParametricPath;
+use fourier_series::svgpath::{Normalization, Origin, Parameterization, SVGPath, SvgLoadError};
+
+const EPSILON: f64 = 1e-9;
+
+fn assert_close(actual: Point, expected: Point) {
+    assert!(
+        actual.approx_eq(expected, EPSILON),
+        "expected {:?}, got {:?}", expected, actual
+    );
+}
+
+#[test]
+fn arc_semicircle_follows_circle() {
+    let path = SVGPath::from_path_data("M 1 0 A 1 1 0 0 1 -1 0").unwrap();
+
+    for i in 0..100 {
+        let t = i as f64 / 100.0;
+        assert_close(path.get_point(t), Point::from_ei(PI * t));
+    }
+}
+
+#[test]
+fn arc_sweep_flag_picks_direction() {
+    let path = SVGPath::from_path_data("M 1 0 A 1 1 0 0 0 -1 0").unwrap();
+
+    assert_close(path.get_point(0.5), Point::new(0.0, -1.0));
+}
+
+#[test]
+fn arc_large_arc_flag_picks_longer_arc() {
+    let path = SVGPath::from_path_data("M 1 0 A 1 1 0 1 1 0 1").unwrap();
+
+    for i in 0..100 {
+        let t = i as f64 / 100.0;
+        assert_close(path.get_point(t), Point::new(1.0, 1.0) + Point::from_ei(1.5 * PI * t - PI / 2.0));
+    }
+}
+
+#[test]
+fn arc_rotated_ellipse() {
+    let path = SVGPath::from_path_data("M 0 2 A 2 1 90 0 1 0 -2").unwrap();
+
+    for i in 0..100 {
+        let t = i as f64 / 100.0;
+        let angle = PI * t;
+        assert_close(path.get_point(t), Point::new(-angle.sin(), 2.0 * angle.cos()));
+    }
+}
+
+#[test]
+fn arc_small_radii_are_scaled_up() {
+    let path = SVGPath::from_path_data("M 2 0 A 0.5 0.5 0 0 1 -2 0").unwrap();
+
+    assert_close(path.get_point(0.0), Point::new(2.0, 0.0));
+    assert_close(path.get_point(0.5), Point::new(0.0, 2.0));
+}
+
+#[test]
+fn arc_zero_radius_is_line() {
+    let path = SVGPath::from_path_data("M 0 0 A 0 3 0 0 1 4 2").unwrap();
+
+    assert_close(path.get_point(0.25), Point::new(1.0, 0.5));
+    assert_close(path.get_point(0.5), Point::new(2.0, 1.0));
+}
+
+fn assert_path_data_samples(data: &str, samples: &[(f64, Point)]) {
+    let path = SVGPath::from_path_data(data).unwrap();
+    for &(t, expected) in samples.iter() {
+        assert_close(path.get_point(t), expected);
+    }
+}
+
+#[test]
+fn smooth_cubic_reflects_previous_control_point() {
+    assert_path_data_samples("M 0 0 C 0 1 1 1 1 0 S 2 -1 2 0", &[(0.75, Point::new(1.5, -0.75))]);
+}
+
+#[test]
+fn smooth_quadratic_reflects_previous_control_point() {
+    assert_path_data_samples("M0 0 Q1 1 2 0 T4 0", &[(0.75, Point::new(3.0, -0.5))]);
+    assert_path_data_samples("M0 0 Q1 1 2 0 T4 0 T6 0", &[(5.0 / 6.0, Point::new(5.0, 0.5))]);
+}
+
+#[test]
+fn smooth_curves_after_other_commands_start_at_current_point() {
+    assert_path_data_samples("M 0 0 L 1 0 S 2 1 3 0", &[(0.75, Point::new(1.625, 0.375))]);
+    assert_path_data_samples("M 0 0 Q 1 1 2 0 S 3 1 4 0", &[(0.75, Point::new(2.625, 0.375))]);
+    assert_path_data_samples("M 0 0 L 2 0 T 4 2", &[(0.75, Point::new(2.5, 0.5))]);
+    assert_path_data_samples("M 0 0 C 0 1 1 1 1 0 T 3 0", &[(0.75, Point::new(1.5, 0.0))]);
+}
+
+#[test]
+fn close_returns_to_subpath_start() {
+    assert_path_data_samples("M 0 0 L 1 0 Z l 0 1", &[
+        (1.0 / 3.0, Point::new(1.0, 0.0)),
+        (0.5, Point::new(0.5, 0.0)),
+        (2.0 / 3.0, Point::new(0.0, 0.0)),
+        (5.0 / 6.0, Point::new(0.0, 0.5))
+    ]);
+}
+
+#[test]
+fn close_at_start_adds_no_segment() {
+    assert_path_data_samples("M 0 0 L 1 0 L 0 0 Z", &[
+        (0.5, Point::new(1.0, 0.0)),
+        (0.75, Point::new(0.5, 0.0))
+    ]);
+}
+
+#[test]
+fn move_with_extra_pairs_draws_lines() {
+    assert_path_data_samples("M 1 1 3 1 3 3", &[
+        (0.5, Point::new(3.0, 1.0)),
+        (0.75, Point::new(3.0, 2.0))
+    ]);
+    assert_path_data_samples("m 1 1 2 0 0 2 z", &[
+        (0.0, Point::new(1.0, 1.0)),
+        (1.0 / 3.0, Point::new(3.0, 1.0)),
+        (2.0 / 3.0, Point::new(3.0, 3.0)),
+        (5.0 / 6.0, Point::new(2.0, 2.0))
+    ]);
+}
+
+#[test]
+fn arc_length_parameterization_has_constant_speed() {
+    let mut path = SVGPath::from_path_data("M 0 0 L 1 0 L 4 0 L 4 4 L 0 4 Z").unwrap();
+    path.set_parameterization(Parameterization::ArcLength);
+
+    assert!((path.get_length() - 16.0).abs() < EPSILON);
+
+    let samples = 160;
+    let step = path.get_length() / samples as f64;
+    let mut last_point = path.get_point(0.0);
+    for i in 1..=samples {
+        let point = path.get_point(i as f64 / samples as f64);
+        assert!(((point - last_point).mag() - step).abs() < 1e-6);
+        last_point = point;
+    }
+}
+
+#[test]
+fn segment_index_parameterization_is_default() {
+    let path = SVGPath::from_path_data("M 0 0 L 1 0 L 4 0 L 4 4 L 0 4 Z").unwrap();
+
+    assert_eq!(path.get_parameterization(), Parameterization::SegmentIndex);
+    assert_close(path.get_point(0.2), Point::new(1.0, 0.0));
+    assert_close(path.get_point(1.0), Point::new(0.0, 0.0));
+}
+
+#[test]
+fn paths_are_joined_into_a_closed_tour() {
+    let path: SVGPath = "<svg><path d=\"M 0 0 L 1 0\"/><path d=\"M 1 1 L 0 1\"/></svg>".parse().unwrap();
+
+    assert_close(path.get_point(0.125), Point::new(0.5, 0.0));
+    // Connecting line from the end of one path to the start of the next
+    assert_close(path.get_point(0.375), Point::new(1.0, 0.5));
+    assert_close(path.get_point(0.625), Point::new(0.5, 1.0));
+    // Closing line back to the start of the first path
+    assert_close(path.get_point(0.875), Point::new(0.0, 0.5));
+    assert_close(path.get_point(1.0), Point::new(0.0, 0.0));
+}
+
+fn write_temp_svg(name: &str, document: &str) -> PathBuf {
+    let file = env::temp_dir().join(format!("fourier-series-{}-{}.svg", name, process::id()));
+    fs::write(&file, document).unwrap();
+    file
+}
+
+#[test]
+fn new_separate_keeps_one_path_per_element() {
+    let document = "<svg viewBox=\"0 0 10 20\"><path d=\"M 0 0 L 1 0\"/><g><path d=\"M 1 1 L 3 1 L 3 3\"/></g></svg>";
+    let file = write_temp_svg("separate", document);
+    let paths = SVGPath::new_separate(&file).unwrap();
+    fs::remove_file(&file).unwrap();
+
+    assert_eq!(paths.len(), 2);
+    assert_close(paths[0].get_point(0.0), Point::new(0.0, 0.0));
+    assert_close(paths[0].get_point(1.0), Point::new(1.0, 0.0));
+    assert_close(paths[1].get_point(0.0), Point::new(1.0, 1.0));
+    assert_close(paths[1].get_point(0.5), Point::new(3.0, 1.0));
+
+    // Every path shares the document's view box
+    for path in paths.iter() {
+        let view_box = path.get_view_box().unwrap();
+        assert_close(view_box.min, Point::new(0.0, 0.0));
+        assert_close(view_box.size, Point::new(10.0, 20.0));
+    }
+}
+
+#[test]
+fn loads_from_document_string() {
+    let document = "<svg><g transform=\"translate(1 2)\"><path d=\"M 0 0 L 2 0\"/></g></svg>";
+    let path: SVGPath = document.parse().unwrap();
+
+    assert_close(path.get_point(0.5), Point::new(2.0, 2.0));
+    assert_close(SVGPath::from_reader(document.as_bytes()).unwrap().get_point(0.0), Point::new(1.0, 2.0));
+}
+
+#[test]
+fn documents_without_drawn_segments_have_no_path() {
+    for document in ["<svg><path d=\"M 1 2\"/></svg>", "<svg><polyline points=\"1,2\"/></svg>"].iter() {
+        assert!(matches!(document.parse::<SVGPath>(), Err(SvgLoadError::NoPathFound)), "{}", document);
+    }
+}
+
+#[test]
+fn skips_shapes_in_non_rendered_containers() {
+    let document = "<svg>\
+        <defs><path d=\"M 10 10 L 20 10\"/></defs>\
+        <clipPath><g><rect width=\"5\" height=\"5\"/></g></clipPath>\
+        <mask><circle r=\"3\"/></mask>\
+        <symbol><line x2=\"7\"/></symbol>\
+        <marker><polygon points=\"0,0 1,1 2,0\"/></marker>\
+        <path d=\"M 0 0 L 2 0\"/>\
+    </svg>";
+    let path: SVGPath = document.parse().unwrap();
+
+    assert_close(path.get_point(0.0), Point::new(0.0, 0.0));
+    assert_close(path.get_point(0.5), Point::new(1.0, 0.0));
+    assert_close(path.get_point(1.0), Point::new(2.0, 0.0));
+}
+
+fn assert_samples(document: &str, samples: &[(f64, Point)]) {
+    let path: SVGPath = document.parse().unwrap();
+    for &(t, expected) in samples.iter() {
+        assert_close(path.get_point(t), expected);
+    }
+}
+
+#[test]
+fn circle_is_traced_from_its_rightmost_point() {
+    let diagonal = 2.0 * (PI / 4.0).cos();
+    assert_samples("<svg><circle cx=\"1\" cy=\"1\" r=\"2\"/></svg>", &[
+        (0.0, Point::new(3.0, 1.0)),
+        (0.125, Point::new(1.0 + diagonal, 1.0 + diagonal)),
+        (0.25, Point::new(1.0, 3.0)),
+        (0.5, Point::new(-1.0, 1.0)),
+        (0.75, Point::new(1.0, -1.0))
+    ]);
+}
+
+#[test]
+fn rounded_rect_has_arcs_at_its_corners() {
+    let diagonal = 2.0 * (PI / 4.0).cos();
+    assert_samples("<svg><rect width=\"10\" height=\"6\" rx=\"2\"/></svg>", &[
+        (0.0, Point::new(2.0, 0.0)),
+        (1.0 / 16.0, Point::new(5.0, 0.0)),
+        (2.0 / 16.0, Point::new(8.0, 0.0)),
+        (3.0 / 16.0, Point::new(8.0 + diagonal, 2.0 - diagonal)),
+        (4.0 / 16.0, Point::new(10.0, 2.0)),
+        (9.0 / 16.0, Point::new(5.0, 6.0)),
+        (15.0 / 16.0, Point::new(2.0 - diagonal, 2.0 - diagonal))
+    ]);
+}
+
+#[test]
+fn rect_with_one_zero_radius_has_square_corners() {
+    assert_samples("<svg><rect width=\"10\" height=\"6\" rx=\"5\" ry=\"0\"/></svg>", &[
+        (0.0, Point::new(0.0, 0.0)),
+        (0.125, Point::new(5.0, 0.0)),
+        (0.25, Point::new(10.0, 0.0)),
+        (0.5, Point::new(10.0, 6.0)),
+        (0.75, Point::new(0.0, 6.0))
+    ]);
+}
+
+#[test]
+fn polygon_is_closed() {
+    assert_samples("<svg><polygon points=\"0,0 4,0 4,3\"/></svg>", &[
+        (1.0 / 6.0, Point::new(2.0, 0.0)),
+        (0.5, Point::new(4.0, 1.5)),
+        (5.0 / 6.0, Point::new(2.0, 1.5)),
+        (1.0, Point::new(0.0, 0.0))
+    ]);
+}
+
+#[test]
+fn load_errors_are_reported() {
+    let error = SVGPath::from_path_data("M 0 0 L 1").err().unwrap();
+    assert!(matches!(error, SvgLoadError::WrongParameterCount('L', 1)), "{:?}", error);
+    assert_eq!(error.to_string(), "wrong number of parameters for path command L: 1");
+
+    let error = SVGPath::from_path_data("M 0 0 L 1 ?").err().unwrap();
+    assert!(matches!(error, SvgLoadError::ParseError(_)), "{:?}", error);
+
+    let error = "<svg><path transform=\"skew(3)\" d=\"M 0 0 L 1 0\"/></svg>".parse::<SVGPath>().err().unwrap();
+    assert!(matches!(&error, SvgLoadError::InvalidTransform(value) if value == "skew(3)"), "{:?}", error);
+    assert_eq!(error.to_string(), "invalid transform attribute: skew(3)");
+
+    let error = "<svg><g/></svg>".parse::<SVGPath>().err().unwrap();
+    assert!(matches!(error, SvgLoadError::NoPathFound), "{:?}", error);
+    assert_eq!(error.to_string(), "the SVG has nothing to draw");
+}
+
+#[test]
+fn normalize_flips_y() {
+    let mut path = SVGPath::from_path_data("M 1 2 L 3 4").unwrap();
+    path.normalize(Normalization { flip_y: true, origin: Origin::Unchanged, fit_to: None });
+
+    assert_close(path.get_point(0.0), Point::new(1.0, -2.0));
+    assert_close(path.get_point(1.0), Point::new(3.0, -4.0));
+}
+
+#[test]
+fn normalize_moves_centroid_to_origin() {
+    let mut path = SVGPath::from_path_data("M 1 1 L 3 1 L 3 3 L 1 3 Z").unwrap();
+    path.normalize(Normalization { flip_y: false, origin: Origin::Centroid, fit_to: None });
+
+    assert_close(path.get_point(0.0), Point::new(-1.0, -1.0));
+    assert_close(path.get_point(0.5), Point::new(1.0, 1.0));
+    assert!(path.get_centroid().approx_eq(Point::ZERO, 1e-6));
+}
+
+#[test]
+fn normalize_moves_view_box_center_to_origin() {
+    let mut path: SVGPath = "<svg viewBox=\"0 0 10 20\"><path d=\"M 5 10 L 6 12\"/></svg>".parse().unwrap();
+    path.normalize(Normalization { flip_y: true, origin: Origin::ViewBoxCenter, fit_to: None });
+
+    assert_close(path.get_point(0.0), Point::new(0.0, 0.0));
+    assert_close(path.get_point(1.0), Point::new(1.0, -2.0));
+
+    let view_box = path.get_view_box().unwrap();
+    assert_close(view_box.min, Point::new(-5.0, -10.0));
+    assert_close(view_box.size, Point::new(10.0, 20.0));
+}
+
+#[test]
+fn normalize_fits_larger_side() {
+    let mut path = SVGPath::from_path_data("M 1 1 L 5 1 L 5 3 L 1 3 Z").unwrap();
+    path.normalize(Normalization { flip_y: false, origin: Origin::Centroid, fit_to: Some(10.0) });
+
+    let (min, max) = path.get_bounds();
+    assert_close(min, Point::new(-5.0, -2.5));
+    assert_close(max, Point::new(5.0, 2.5));
+}
+
+#[test]
+fn shapes_without_size_are_not_drawn() {
+    let shapes = [
+        "<rect x=\"50\" y=\"50\"/>",
+        "<rect x=\"50\" y=\"50\" width=\"10\"/>",
+        "<rect width=\"-10\" height=\"10\"/>",
+        "<circle cx=\"50\" cy=\"50\"/>",
+        "<ellipse cx=\"50\" cy=\"50\" rx=\"10\"/>"
+    ];
+
+    for shape in shapes.iter() {
+        let document = format!("<svg>{}<path d=\"M 0 0 L 10 0\"/></svg>", shape);
+        let path: SVGPath = document.parse().unwrap();
+
+        assert_close(path.get_point(0.0), Point::new(0.0, 0.0));
+        assert_close(path.get_point(0.5), Point::new(5.0, 0.0));
+        assert_close(path.get_point(1.0), Point::new(10.0, 0.0));
+    }
+}
+
+#[test]
+fn lengths_accept_absolute_units() {
+    assert_samples("<svg><rect width=\"1in\" height=\"6pt\"/></svg>", &[
+        (0.25, Point::new(96.0, 0.0)),
+        (0.5, Point::new(96.0, 8.0))
+    ]);
+}
+
+#[test]
+fn relative_lengths_are_rejected() {
+    let error = "<svg><rect width=\"50%\" height=\"10\"/></svg>".parse::<SVGPath>().err().unwrap();
+
+    assert!(matches!(&error, SvgLoadError::InvalidLength(name, value) if name == "width" && value == "50%"), "{:?}", error);
+    assert_eq!(error.to_string(), "unsupported length for width: 50%");
+}
+
+#[test]
+fn nested_group_transforms_apply_innermost_first() {
+    let document = "<svg><g transform=\"translate(10 0)\"><g transform=\"scale(2)\">\
+        <path transform=\"translate(0 1)\" d=\"M 0 0 L 1 0\"/>\
+    </g></g></svg>";
+    let path: SVGPath = document.parse().unwrap();
+
+    assert_close(path.get_point(0.0), Point::new(10.0, 2.0));
+    assert_close(path.get_point(1.0), Point::new(12.0, 2.0));
+}
+
+#[test]
+fn group_transform_ends_with_the_group() {
+    let document = "<svg>\
+        <g transform=\"translate(100 0)\"><path d=\"M 0 0 L 1 0\"/></g>\
+        <g transform=\"scale(5)\"/>\
+        <path d=\"M 0 5 L 1 5\"/>\
+    </svg>";
+    let path: SVGPath = document.parse().unwrap();
+
+    assert_close(path.get_point(0.0), Point::new(100.0, 0.0));
+    assert_close(path.get_point(0.5), Point::new(0.0, 5.0));
+    assert_close(path.get_point(0.625), Point::new(0.5, 5.0));
+}
+
+#[test]
+fn arc_under_non_uniform_scale_becomes_elliptical() {
+    let path: SVGPath = "<svg><path transform=\"scale(2 1)\" d=\"M 1 0 A 1 1 0 0 1 -1 0\"/></svg>".parse().unwrap();
+    let diagonal = (PI / 4.0).cos();
+
+    assert_close(path.get_point(0.0), Point::new(2.0, 0.0));
+    assert_close(path.get_point(0.25), Point::new(2.0 * diagonal, diagonal));
+    assert_close(path.get_point(0.5), Point::new(0.0, 1.0));
+    assert_close(path.get_point(1.0), Point::new(-2.0, 0.0));
+}
@@ -0,0 +1,36 @@
+use fourier_series::common::Point;
+use fourier_series::transform::Transform;
+
+fn assert_maps(value: &str, from: (f64, f64), to: (f64, f64)) {
+    let transform = Transform::parse(value).unwrap();
+    let actual = transform.apply(Point::new(from.0, from.1));
+    assert!(actual.approx_eq(to, 1e-9), "{} maps {:?} to {:?}, expected {:?}", value, from, actual, to);
+}
+
+#[test]
+fn parses_each_function() {
+    assert_maps("matrix(1 2 3 4 5 6)", (1.0, 1.0), (9.0, 12.0));
+    assert_maps("translate(3)", (1.0, 1.0), (4.0, 1.0));
+    assert_maps("translate(3, -2)", (1.0, 1.0), (4.0, -1.0));
+    assert_maps("scale(2)", (1.0, 1.0), (2.0, 2.0));
+    assert_maps("scale(2 3)", (1.0, 1.0), (2.0, 3.0));
+    assert_maps("rotate(90)", (1.0, 0.0), (0.0, 1.0));
+    assert_maps("rotate(90 1 1)", (2.0, 1.0), (1.0, 2.0));
+    assert_maps("skewX(45)", (0.0, 1.0), (1.0, 1.0));
+    assert_maps("skewY(45)", (1.0, 0.0), (1.0, 1.0));
+}
+
+#[test]
+fn later_functions_apply_first() {
+    assert_maps("translate(10 0) scale(2)", (1.0, 1.0), (12.0, 2.0));
+    assert_maps("scale(2) translate(10 0)", (1.0, 1.0), (22.0, 2.0));
+    assert_maps("translate(10,0),rotate(90)", (1.0, 0.0), (10.0, 1.0));
+}
+
+#[test]
+fn rejects_unknown_functions_and_argument_counts() {
+    assert_eq!(Transform::parse("skew(3)"), None);
+    assert_eq!(Transform::parse("rotate(1 2)"), None);
+    assert_eq!(Transform::parse("scale(a)"), None);
+    assert_eq!(Transform::parse(""), Some(Transform::IDENTITY));
+}
@@ -0,0 +1,33 @@
+use std::f64::consts::PI;
+
+use fourier_series::common::Point;
+use fourier_series::world::{SVector, World};
+
+fn world() -> World {
+    World::new(vec![
+        SVector::new(0.0, 0.0, 1.0),
+        SVector::new(PI / 2.0, 1.0, 2.0),
+        SVector::new(0.0, -3.0, 0.5)
+    ])
+}
+
+#[test]
+fn tip_sums_every_vector() {
+    let world = world();
+
+    assert!(world.tip(0.0).approx_eq((1.5, 2.0), 1e-12));
+    assert!(world.tip(0.25).approx_eq((-1.0, 0.5), 1e-12));
+}
+
+#[test]
+fn sample_path_matches_tip() {
+    let world = world();
+    let samples = world.sample_path(7);
+
+    assert_eq!(samples.len(), 7);
+    for (i, &point) in samples.iter().enumerate() {
+        assert_eq!(point, world.tip(i as f64 / 7.0));
+    }
+    assert!(world.sample_path(0).is_empty());
+    assert_eq!(World::new(Vec::new()).sample_path(3), vec![Point::ZERO; 3]);
+}